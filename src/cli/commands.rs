@@ -0,0 +1,869 @@
+use super::{
+    CompareArgs, DownloadArgs, FileArgs, LocalArgs, RunArgs, RunCommandArgs, StressArgs,
+    SubmissionsArgs, SubmitArgs,
+};
+use crate::compare;
+use crate::config::{Config, OutputFormat};
+use crate::export;
+use crate::local;
+use crate::server;
+use crate::stress::{QuestionSpec, StressOutcome, StressTest};
+use crate::tui;
+use crate::watch::{FileWatcher, DEFAULT_DEBOUNCE};
+use colored::Colorize;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::language::Language;
+use leetcode_runner_cli::file_parser::line_map::remap_lines;
+use leetcode_runner_cli::file_parser::testcases::Testcases;
+use leetcode_runner_cli::leetcode_api::event::Event;
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use leetcode_runner_cli::leetcode_api::metadata::MetaData;
+use leetcode_runner_cli::leetcode_api::policy::SubmitPolicy;
+use leetcode_runner_cli::leetcode_api::worker::{ExecutionResult, SubmissionResult};
+use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Authenticates with the cookie from the `LC_COOKIE` environment variable,
+/// against the region and with the poll timing of the config
+pub fn authenticate(config: &Config) -> Result<LeetCode<Authorized>, String> {
+    let format = config.output.format.unwrap_or_default();
+    authenticate_with(config, move |event| notice(format, event))
+}
+
+/// Same as `authenticate`, with the progress of runs and submissions
+/// reported to `on_event`
+fn authenticate_with(
+    config: &Config,
+    on_event: impl Fn(&Event) + Send + Sync + 'static,
+) -> Result<LeetCode<Authorized>, String> {
+    let key = "LC_COOKIE";
+    let Some(cookie) = std::env::var_os(key) else {
+        return Err(format!("{} is not set in the environment.", key));
+    };
+    let Some(cookie) = cookie.to_str() else {
+        return Err("Invalid unicode in cookie".to_string());
+    };
+    LeetCode::new()
+        .region(config.region.unwrap_or_default())
+        .timing(config.timing())
+        .on_event(on_event)
+        .authenticate(cookie)
+}
+
+pub fn auth(lc: &LeetCode<Authorized>) -> ExitCode {
+    match lc.get_metadata() {
+        Ok(metadata) => {
+            println!("Authenticated successfully!\n");
+            metadata.display();
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("Authentication Error : {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn show(lc: &LeetCode<Authorized>, question: &str) -> ExitCode {
+    let question_content = match lc.question_content(question) {
+        Ok(question_content) => question_content,
+        Err(e) => {
+            println!("Some error occured! {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let filename = format!("{}.html", question);
+    if std::fs::write(&filename, question_content.content).is_ok() {
+        println!("Saved question as HTML to {}", filename);
+        ExitCode::SUCCESS
+    } else {
+        println!("Error saving question as HTML");
+        ExitCode::FAILURE
+    }
+}
+
+pub fn submissions(lc: &LeetCode<Authorized>, args: SubmissionsArgs) -> ExitCode {
+    let page = args.page.max(1);
+    // every page is fetched with the key of the one before
+    let mut last_key = String::new();
+    for current in 1..=page {
+        let offset = (current - 1) * args.limit;
+        let submissions =
+            match lc.submissions(args.question.as_deref(), offset, args.limit, &last_key) {
+                Ok(submissions) => submissions,
+                Err(e) => {
+                    println!("Some error occured! {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+        if current == page {
+            submissions.display();
+            if submissions.has_next {
+                println!("\nMore submissions on --page {}", page + 1);
+            }
+            break;
+        }
+        if !submissions.has_next {
+            println!(
+                "No submissions on page {}, the last page is {}",
+                page, current
+            );
+            return ExitCode::FAILURE;
+        }
+        last_key = submissions.last_key;
+    }
+    ExitCode::SUCCESS
+}
+
+pub fn download(lc: &LeetCode<Authorized>, args: DownloadArgs, config: &Config) -> ExitCode {
+    match download_submission(lc, &args.target, args.output, config) {
+        Ok(path) => {
+            println!("Saved submission to {}", path);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("Some error occured! {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn export(lc: &LeetCode<Authorized>, dir: &str, config: &Config) -> ExitCode {
+    let region = config.region.unwrap_or_default();
+    match export::export(lc, std::path::Path::new(dir), region) {
+        Ok(summary) => {
+            summary.display();
+            if summary.failed.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            println!("Some error occured! {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn compare(lc: &LeetCode<Authorized>, args: CompareArgs) -> ExitCode {
+    let codefiles = args
+        .files
+        .iter()
+        .map(|file| CodeFile::try_from_file(Path::new(file)))
+        .collect();
+    let codefiles = match codefiles {
+        Ok(codefiles) => codefiles,
+        Err(e) => {
+            println!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let data_input = match read_testcases(&args.testcase) {
+        Ok(data_input) => data_input,
+        Err(e) => {
+            println!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match compare::compare(lc, codefiles, &data_input) {
+        Ok(comparison) => {
+            comparison.display();
+            if comparison.disagreements().is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            println!("Some error occured! {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The progress of runs and submissions is shown on the screen of the tui
+pub fn tui(config: &Config) -> ExitCode {
+    let (sender, progress) = std::sync::mpsc::channel();
+    let lc = match authenticate_with(config, move |event| {
+        let _ = sender.send(event.to_string());
+    }) {
+        Ok(lc) => lc,
+        Err(e) => {
+            println!("Authentication Error : {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match tui::run(&lc, progress, config) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            println!("Some error occured! {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn shell(lc: &LeetCode<Authorized>, args: FileArgs, config: &Config) -> ExitCode {
+    super::shell::run(lc, config, args.file)
+}
+
+/// Stdout carries the protocol, errors go to stderr
+pub fn serve(config: &Config) -> ExitCode {
+    colored::control::set_override(false);
+    match server::run(config) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn local(args: LocalArgs, config: &Config) -> ExitCode {
+    let code = load_code(args.file, config);
+    let input = match read_testcases(&args.testcase) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let timeout = args
+        .timeout
+        .or(config.local.timeout_secs)
+        .map_or(local::runner::DEFAULT_TIMEOUT, Duration::from_secs);
+    match local::runner::run(&code, &input, timeout) {
+        Ok(result) => {
+            result.display();
+            if result.is_success() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            println!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn stress(lc: &LeetCode<Authorized>, args: StressArgs, config: &Config) -> ExitCode {
+    let code = load_code(args.file, config);
+    let spec = match QuestionSpec::fetch(lc, &code.question_title) {
+        Ok(spec) => spec,
+        Err(e) => {
+            println!("Some error occured! {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let stress_test = StressTest {
+        batch_size: args.batch_size,
+        max_iterations: args.iterations,
+        time_budget: Duration::from_secs(args.time_budget),
+        delay: Duration::from_secs(args.delay),
+    };
+    match stress_test.run(lc, &code, &spec) {
+        Ok(StressOutcome::Passed {
+            iterations,
+            testcases,
+        }) => {
+            println!(
+                "{}",
+                format!(
+                    "No mismatch found in {} testcases over {} iterations",
+                    testcases, iterations
+                )
+                .green()
+                .bold()
+            );
+            ExitCode::SUCCESS
+        }
+        Ok(StressOutcome::Failed {
+            input,
+            output,
+            expected,
+            failed,
+        }) => {
+            println!(
+                "\n{}\n\nInput    :\n{}\n\nOutput   : {}\nExpected : {}\n",
+                "Mismatch found!".red().bold(),
+                input.cyan(),
+                output,
+                expected
+            );
+            save_regressions(
+                &failed,
+                &code.question_title,
+                &spec.meta_data,
+                OutputFormat::Text,
+            );
+            ExitCode::FAILURE
+        }
+        Ok(StressOutcome::Aborted { result, testcases }) => {
+            display_execution_failure(&result, &code);
+            println!(
+                "\nInputs of the batch :\n{}\n",
+                testcases.to_string().cyan()
+            );
+            save_regressions(
+                &testcases,
+                &code.question_title,
+                &spec.meta_data,
+                OutputFormat::Text,
+            );
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            println!("Some error occured! {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn run(lc: &LeetCode<Authorized>, args: RunCommandArgs, config: &Config) -> ExitCode {
+    if args.watch {
+        let debounce = args
+            .debounce
+            .or(config.watch.debounce_ms)
+            .map_or(DEFAULT_DEBOUNCE, Duration::from_millis);
+        return watch(lc, args.run, debounce, config);
+    }
+    let args = args.run;
+    let code = load_code(args.file, config);
+    let verbose = args.verbose || config.output.verbose.unwrap_or_default();
+    let format = config.output.format.unwrap_or_default();
+    if args.check && !check(&code, format) {
+        return ExitCode::FAILURE;
+    }
+    let run = match execute(
+        lc,
+        &code,
+        args.testcase,
+        args.generate,
+        args.testcase_out,
+        format,
+    ) {
+        Ok(run) => run,
+        Err(e) => {
+            print_error(format, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if display_run(&run, &code, verbose, format) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs the testcases again every time the extracted code or the
+/// testcase file changes, until interrupted
+fn watch(
+    lc: &LeetCode<Authorized>,
+    args: RunArgs,
+    debounce: Duration,
+    config: &Config,
+) -> ExitCode {
+    let code = load_code(args.file, config);
+    let verbose = args.verbose || config.output.verbose.unwrap_or_default();
+    let format = config.output.format.unwrap_or_default();
+    let mut files = vec![code.path.as_path()];
+    if let Some(testcase) = &args.testcase {
+        files.push(std::path::Path::new(testcase));
+    }
+    let watcher = match FileWatcher::new(&files, debounce) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            print_error(format, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let header = format!(
+        "Watching {} for changes, press Ctrl-C to stop\n",
+        code.path.display()
+    );
+
+    // question, extracted code and testcases of the shown results
+    let mut last_run: Option<(String, String, String)> = None;
+    loop {
+        match (code.reload(), read_testcases(&args.testcase)) {
+            (Ok(code), Ok(data_input)) => {
+                let run_key = (
+                    code.question_title.clone(),
+                    code.code.clone(),
+                    data_input.clone(),
+                );
+                // saving without touching the extracted code doesn't run again
+                if last_run.as_ref() != Some(&run_key) {
+                    show_header(&header, format);
+                    if !args.check || check(&code, format) {
+                        let run = (lc.execute(&code, data_input), None);
+                        display_run(&run, &code, verbose, format);
+                    }
+                    last_run = Some(run_key);
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                show_header(&header, format);
+                print_error(format, e);
+                last_run = None;
+            }
+        }
+        if let Err(e) = watcher.wait() {
+            print_error(format, e);
+            return ExitCode::FAILURE;
+        }
+    }
+}
+
+/// Starts the output of a run on a cleared screen, in JSON mode the
+/// results are kept one after the other and the header goes to stderr
+fn show_header(header: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            clear_screen();
+            println!("{}", header.dimmed());
+        }
+        OutputFormat::Json => eprintln!("{}", header),
+    }
+}
+
+/// Clears the terminal and moves the cursor to the top left
+fn clear_screen() {
+    print!("\x1B[2J\x1B[1;1H");
+    let _ = std::io::stdout().flush();
+}
+
+pub fn submit(lc: &LeetCode<Authorized>, args: SubmitArgs, config: &Config) -> ExitCode {
+    let policy = if args.skip_run {
+        SubmitPolicy::SkipRun
+    } else if args.force {
+        SubmitPolicy::Always
+    } else {
+        config.submit.policy.unwrap_or_default()
+    };
+    let format = config.output.format.unwrap_or_default();
+    let verbose = args.run.verbose || config.output.verbose.unwrap_or_default();
+    let run_args = args.run;
+    let code = load_code(run_args.file, config);
+    if run_args.check && !check(&code, format) {
+        return ExitCode::FAILURE;
+    }
+
+    let run = if policy.runs_testcases() {
+        let run = match execute(
+            lc,
+            &code,
+            run_args.testcase,
+            run_args.generate,
+            run_args.testcase_out,
+            format,
+        ) {
+            Ok(run) => run,
+            Err(e) => {
+                print_error(format, e);
+                return ExitCode::FAILURE;
+            }
+        };
+        // in JSON mode the run is printed along with the submission
+        review_run(&run, &code, verbose, format);
+        Some(run.0)
+    } else {
+        None
+    };
+    if !policy.should_submit(run.as_ref()) {
+        let reason = match &run {
+            Some(Ok(ExecutionResult::Success(_))) => {
+                "Aborting submission due to failed testcase(s)!"
+            }
+            _ => "Aborting submission as the testcases couldn't be run!",
+        };
+        match format {
+            OutputFormat::Text => {
+                println!("{}", reason.red().bold());
+                println!("Use --force to submit anyway");
+            }
+            OutputFormat::Json => {
+                display_submission(lc, run.as_ref(), Err(reason.to_string()), &code, format);
+            }
+        }
+        return ExitCode::FAILURE;
+    }
+    if !args.yes && config.submit.confirm.unwrap_or(true) {
+        notice(format, format_args!("\n{}\n", code.preview()));
+        if !confirm("Submit this code?") {
+            let cancelled = "Submission cancelled";
+            match format {
+                OutputFormat::Text => println!("{}", cancelled.yellow().bold()),
+                OutputFormat::Json => {
+                    display_submission(lc, run.as_ref(), Err(cancelled.to_string()), &code, format);
+                }
+            }
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let result = lc.submit(&code);
+    if display_submission(lc, run.as_ref(), result, &code, format) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Prints the outcome of the submission, `true` when it got accepted.
+/// In JSON mode the run before it (if any) is part of the same document,
+/// `{"run": ..., "submission": ...}`.
+pub(super) fn display_submission(
+    lc: &LeetCode<Authorized>,
+    run: Option<&Result<ExecutionResult, String>>,
+    result: Result<SubmissionResult, String>,
+    code: &CodeFile,
+    format: OutputFormat,
+) -> bool {
+    if format == OutputFormat::Json {
+        let document = serde_json::json!({
+            "run": run.map(json_value),
+            "submission": json_value(&result),
+        });
+        print_json(&Ok::<_, String>(document));
+        return matches!(result, Ok(SubmissionResult::Success(_)));
+    }
+    match result {
+        Ok(result) => match result {
+            SubmissionResult::Success(success) => {
+                success.display();
+                // the charts are extra, the submission is accepted regardless
+                match lc.submission_distributions(&success.submission_id) {
+                    Ok(distributions) => {
+                        distributions.display(&success.status_runtime, &success.status_memory)
+                    }
+                    Err(e) => println!("Couldn't fetch the distributions : {e}"),
+                }
+                true
+            }
+            SubmissionResult::LimitExceeded(wrong) => {
+                wrong.display();
+                false
+            }
+            SubmissionResult::PendingResult(state) => {
+                println!("Pending Result!");
+                println!("State : {:?}", state.state());
+                false
+            }
+            SubmissionResult::CompileError(compile_err) => {
+                println!(
+                    "\nSubmission failed due to Compile Error!\nError Message :\n{}\n\nFull error message :\n{}",
+                    remap_lines(code, &compile_err.compile_error),
+                    remap_lines(code, &compile_err.full_compile_error)
+                );
+                false
+            }
+            SubmissionResult::RuntimeError(runtime_error) => {
+                println!(
+                    "\nSubmission failed due to Runtime Error!\nError Message :\n{}\n\nFull error message :\n{}",
+                    remap_lines(code, &runtime_error.runtime_error),
+                    remap_lines(code, &runtime_error.full_runtime_error)
+                );
+                false
+            }
+            SubmissionResult::Wrong(wrong) => {
+                wrong.display();
+                false
+            }
+            SubmissionResult::Unknown(_) => {
+                println!("Unknown Error!");
+                false
+            }
+        },
+        Err(e) => {
+            println!("Some error occured! {e}");
+            false
+        }
+    }
+}
+
+/// Code file given, or picked from the current directory, created
+/// if there is none. Exits when the file can't be read.
+pub(super) fn load_code(file: FileArgs, config: &Config) -> CodeFile {
+    let language = config.language();
+    let code = match file.file {
+        Some(file) => CodeFile::try_from_file(Path::new(&file)),
+        None => {
+            let file_template = config.file_template.as_deref();
+            CodeFile::from_dir(language.as_ref(), file_template).and_then(|code| match code {
+                Some(code) => Ok(code),
+                None => {
+                    let region = config.region.unwrap_or_default();
+                    let code = CodeFile::create_default(language.as_ref(), region)?;
+                    notice(
+                        config.output.format.unwrap_or_default(),
+                        format_args!(
+                            "No code file found. Created a new file named {}",
+                            code.path.display()
+                        ),
+                    );
+                    Ok(code)
+                }
+            })
+        }
+    };
+    code.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+/// Contents of the testcase file, empty when none is given
+pub(super) fn read_testcases(testcase: &Option<String>) -> Result<String, String> {
+    match testcase {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|_| format!("Error reading testcases from {}", path)),
+        None => Ok(String::new()),
+    }
+}
+
+/// Local compile check, `false` when the code doesn't compile.
+/// In JSON mode a failed check is the result of the command.
+pub(super) fn check(code: &CodeFile, format: OutputFormat) -> bool {
+    let check = local::check::check(code);
+    match (format, &check) {
+        (OutputFormat::Text, Ok(check)) => check.display(),
+        (OutputFormat::Text, Err(e)) => println!("{e}"),
+        (OutputFormat::Json, Ok(check)) if check.success => {}
+        (OutputFormat::Json, Ok(check)) => {
+            print_json(&Ok::<_, String>(serde_json::json!({ "check": check })))
+        }
+        (OutputFormat::Json, Err(e)) => print_error(format, e.clone()),
+    }
+    check.is_ok_and(|check| check.success)
+}
+
+/// Outcome of running testcases on leetcode, along with the generated
+/// testcases (if any) to save the failing ones of
+pub(super) type Run = (
+    Result<ExecutionResult, String>,
+    Option<(Testcases, MetaData)>,
+);
+
+/// Runs the testcases of the file, the generated ones or the default ones.
+/// An error when the testcases can't be put together.
+fn execute(
+    lc: &LeetCode<Authorized>,
+    code: &CodeFile,
+    testcase: Option<String>,
+    generate: Option<usize>,
+    testcase_out: Option<String>,
+    format: OutputFormat,
+) -> Result<Run, String> {
+    if let Some(count) = generate {
+        let spec = QuestionSpec::fetch(lc, &code.question_title)
+            .map_err(|e| format!("Some error occured! {e}"))?;
+        let testcases = spec.generator().generate(count)?;
+        let path = match testcase_out {
+            Some(path) => path.into(),
+            None => Testcases::generated_path(&code.question_title),
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if std::fs::write(&path, format!("{}\n", testcases)).is_err() {
+            return Err(format!(
+                "Error saving generated testcases to {}",
+                path.display()
+            ));
+        }
+        notice(
+            format,
+            format_args!(
+                "Generated {} testcases to {}",
+                testcases.len(),
+                path.display()
+            ),
+        );
+        let data_input = testcases.to_string();
+        return Ok((
+            lc.execute(code, data_input),
+            Some((testcases, spec.meta_data)),
+        ));
+    }
+    let data_input = read_testcases(&testcase)?;
+    Ok((lc.execute(code, data_input), None))
+}
+
+/// Prints the outcome of the run, `true` when every testcase passed
+pub(super) fn display_run(run: &Run, code: &CodeFile, verbose: bool, format: OutputFormat) -> bool {
+    if format == OutputFormat::Json {
+        print_json(&run.0);
+    }
+    review_run(run, code, verbose, format)
+}
+
+/// Prints the outcome of the run as text, nothing in JSON mode, and
+/// saves the generated testcases that failed.
+/// `true` when every testcase passed.
+pub(super) fn review_run(run: &Run, code: &CodeFile, verbose: bool, format: OutputFormat) -> bool {
+    let (result, generated) = run;
+    match result {
+        Ok(ExecutionResult::Success(result)) => {
+            if format == OutputFormat::Text {
+                result.display(verbose);
+            }
+            if let Some((testcases, meta_data)) = generated {
+                let failed = testcases.select(&result.failed_cases());
+                save_regressions(&failed, &code.question_title, meta_data, format);
+            }
+            result.is_correct()
+        }
+        Ok(result) => {
+            if format == OutputFormat::Text {
+                display_execution_failure(result, code);
+            }
+            false
+        }
+        Err(e) => {
+            if format == OutputFormat::Text {
+                println!("Some error occured! {e}");
+            }
+            false
+        }
+    }
+}
+
+/// Prints the result as JSON, errors as `{"error": <message>}`
+fn print_json<T: Serialize>(result: &Result<T, String>) {
+    match serde_json::to_string_pretty(&json_value(result)) {
+        Ok(json) => println!("{}", json),
+        Err(e) => println!("Failed to serialize the result : {e}"),
+    }
+}
+
+/// The result as a JSON value, errors as `{"error": <message>}`
+fn json_value<T: Serialize>(result: &Result<T, String>) -> serde_json::Value {
+    match result.as_ref().map(serde_json::to_value) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => {
+            serde_json::json!({ "error": format!("Failed to serialize the result : {e}") })
+        }
+        Err(e) => serde_json::json!({ "error": e }),
+    }
+}
+
+/// Prints the error, as `{"error": <message>}` in JSON mode
+pub fn print_error(format: OutputFormat, error: String) {
+    match format {
+        OutputFormat::Text => println!("{error}"),
+        OutputFormat::Json => print_json(&Err::<(), _>(error)),
+    }
+}
+
+/// Prints what isn't the result of the command, on stderr in JSON mode
+/// so that stdout only holds the JSON document
+fn notice(format: OutputFormat, message: impl std::fmt::Display) {
+    match format {
+        OutputFormat::Text => println!("{message}"),
+        OutputFormat::Json => eprintln!("{message}"),
+    }
+}
+
+/// Asks a yes / no question on the terminal, anything but yes is a no.
+/// Without a terminal to ask on, the answer is no. The question is asked
+/// on stderr, leaving stdout to the results.
+pub(super) fn confirm(prompt: &str) -> bool {
+    if !std::io::stdin().is_terminal() {
+        eprintln!("Not running in a terminal, pass --yes to submit without confirmation");
+        return false;
+    }
+    eprint!("{} [y/N] ", prompt.bold());
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Prints why the execution didn't succeed
+fn display_execution_failure(result: &ExecutionResult, code: &CodeFile) {
+    match result {
+        ExecutionResult::Success(_) => {}
+        ExecutionResult::LimitExceeded(limit_exceeded) => {
+            println!("{}", limit_exceeded.status_msg);
+            println!("Time Elapsed : {}", limit_exceeded.elapsed_time);
+            println!("Memory : {}", limit_exceeded.memory);
+        }
+        ExecutionResult::CompileError(compile_error) => {
+            println!(
+                "Compile Error!\nError Message : {}\n\nFull error message :\n{}",
+                remap_lines(code, &compile_error.compile_error),
+                remap_lines(code, &compile_error.full_compile_error)
+            );
+        }
+        ExecutionResult::RuntimeError(runtime_error) => {
+            println!(
+                "Runtime Error!\nError Message : {}\n\nFull error message :\n{}",
+                remap_lines(code, &runtime_error.runtime_error),
+                remap_lines(code, &runtime_error.full_runtime_error)
+            );
+        }
+        ExecutionResult::PendingResult(state) => {
+            println!("Pending Result!");
+            println!("State : {:?}", state.state());
+        }
+        ExecutionResult::Unknown(_) => {
+            println!("Unknown Error!");
+        }
+    }
+}
+
+/// Saves the failed testcases to the regressions of the question
+fn save_regressions(
+    failed: &Testcases,
+    question_title: &str,
+    meta_data: &MetaData,
+    format: OutputFormat,
+) {
+    if failed.is_empty() {
+        return;
+    }
+    match failed.save_regressions(question_title, meta_data) {
+        Ok(()) => notice(
+            format,
+            format_args!(
+                "Saved {} failed testcase(s) to {}",
+                failed.len(),
+                Testcases::regressions_path(question_title).display()
+            ),
+        ),
+        Err(e) => notice(format, e),
+    }
+}
+
+/// Writes the code of a past submission to a file `CodeFile` can read back,
+/// `target` being either a submission id or a question title slug
+fn download_submission(
+    lc: &LeetCode<Authorized>,
+    target: &str,
+    output: Option<String>,
+    config: &Config,
+) -> Result<String, String> {
+    let submission = match target.parse::<u64>() {
+        Ok(id) => lc.submission_code(id)?,
+        Err(_) => lc.latest_accepted(target)?,
+    };
+    let Some(language) = Language::from_name(&submission.lang) else {
+        return Err(format!("Unsupported language {}", submission.lang));
+    };
+    let path = output.unwrap_or_else(|| config.file_name(&submission.title_slug, &language));
+    if std::path::Path::new(&path).exists() {
+        return Err(format!("{} already exists", path));
+    }
+    let region = config.region.unwrap_or_default();
+    let contents = CodeFile::template(&language, region, &submission.title_slug, &submission.code);
+    std::fs::write(&path, contents).map_err(|_| format!("Failed to write {}", path))?;
+    Ok(path)
+}
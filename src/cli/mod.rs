@@ -0,0 +1,171 @@
+pub mod commands;
+mod shell;
+
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Authenticate with LeetCode
+    Auth,
+    /// Save question as HTML
+    Show {
+        /// Title slug of the question, e.g. two-sum
+        question: String,
+    },
+    /// Execute testcases on leetcode
+    Run(RunCommandArgs),
+    /// Submit the code, after running the testcases
+    Submit(SubmitArgs),
+    /// Compile and run the whole file locally, driver code included
+    Local(LocalArgs),
+    /// Stress test the code with random testcases until
+    /// the output differs from the expected one
+    Stress(StressArgs),
+    /// List past submissions of the question, or of every question
+    #[command(alias = "list")]
+    Submissions(SubmissionsArgs),
+    /// Download the code of a past submission
+    Download(DownloadArgs),
+    /// Export the latest accepted solution of every question,
+    /// along with the question statements
+    Export {
+        /// Directory to export into
+        dir: String,
+    },
+    /// Run several solutions of the same question against
+    /// the same testcases and compare their results
+    Compare(CompareArgs),
+    /// Browse problems, read statements, run and submit in one workspace
+    Tui,
+    /// Interactive shell keeping the client and the question between commands
+    Shell(FileArgs),
+    /// JSON-RPC server over stdio, for editors and other tools
+    Serve,
+}
+
+#[derive(Args, Debug)]
+pub struct FileArgs {
+    /// Code to run or submit, picked from the current directory if not given
+    #[arg(short, long)]
+    pub file: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub file: FileArgs,
+    /// Executes the testcases from given file instead of the default ones
+    #[arg(short, long)]
+    pub testcase: Option<String>,
+    /// Generate given number of random testcases and execute them,
+    /// failing testcases are saved as regressions
+    #[arg(short, long, conflicts_with = "testcase")]
+    pub generate: Option<usize>,
+    /// File generated testcases are written to
+    #[arg(long, requires = "generate", value_name = "FILE")]
+    pub testcase_out: Option<String>,
+    /// Compile the extracted code locally before running it
+    #[arg(short, long, action)]
+    pub check: bool,
+    /// Show the input of passing testcases too
+    #[arg(short, long, action)]
+    pub verbose: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RunCommandArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+    /// Run the testcases again whenever the extracted code
+    /// or the testcase file changes
+    #[arg(short, long, action, conflicts_with = "generate")]
+    pub watch: bool,
+    /// Milliseconds without changes to wait for before running again
+    #[arg(long, requires = "watch", value_name = "MS")]
+    pub debounce: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+    /// Submit even when the testcases fail or can't be run
+    #[arg(long, action)]
+    pub force: bool,
+    /// Submit the code without running the testcases first
+    #[arg(long, action, conflicts_with_all = ["force", "testcase", "generate"])]
+    pub skip_run: bool,
+    /// Submit without showing the code and asking for confirmation
+    #[arg(short, long, action)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct LocalArgs {
+    #[command(flatten)]
+    pub file: FileArgs,
+    /// File used as the standard input
+    #[arg(short, long)]
+    pub testcase: Option<String>,
+    /// Seconds the program may run for before it gets killed
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+pub struct StressArgs {
+    #[command(flatten)]
+    pub file: FileArgs,
+    /// Random testcases run on every iteration
+    #[arg(short, long, default_value_t = 10)]
+    pub batch_size: usize,
+    /// Maximum iterations of the stress test
+    #[arg(long, default_value_t = 20)]
+    pub iterations: usize,
+    /// Time budget of the stress test in seconds
+    #[arg(long, default_value_t = 600)]
+    pub time_budget: u64,
+    /// Seconds to wait between stress test iterations
+    #[arg(long, default_value_t = 3)]
+    pub delay: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmissionsArgs {
+    /// Title slug of the question, every question if not given
+    pub question: Option<String>,
+    /// Page of the submissions to list
+    #[arg(long, default_value_t = 1)]
+    pub page: usize,
+    /// Submissions listed per page
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct DownloadArgs {
+    /// Id of the submission, or the question to download
+    /// the latest accepted submission of
+    #[arg(value_name = "ID|QUESTION")]
+    pub target: String,
+    /// File to write to, <question>.<extension> if not given
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// Solutions to compare
+    #[arg(required = true, num_args = 2..)]
+    pub files: Vec<String>,
+    /// Executes the testcases from given file instead of the default ones
+    #[arg(short, long)]
+    pub testcase: Option<String>,
+}
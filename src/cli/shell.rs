@@ -0,0 +1,318 @@
+use super::commands::{self, Run};
+use crate::config::Config;
+use colored::Colorize;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::testcases::Testcases;
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use leetcode_runner_cli::leetcode_api::metadata::MetaData;
+use leetcode_runner_cli::leetcode_api::policy::SubmitPolicy;
+use leetcode_runner_cli::leetcode_api::problems::Problem;
+use leetcode_runner_cli::leetcode_api::worker::Question;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// History of the shell, inside the user data directory
+const HISTORY_FILE: &str = "leetcode-runner-cli/shell_history";
+const NO_SOLUTION: &str = "No solution open, use open <FILE|QUESTION> first";
+const HELP: &str = "\
+open <FILE|QUESTION>   Work on a solution file, or on a question (its file is created if missing)
+show                   Show the statement of the question
+run [TESTCASE_FILE]    Run the testcases, the default ones if no file is given
+submit [--force] [-y]  Submit the solution, after running the testcases
+add-case               Add a testcase to the custom testcases of the question
+next                   Move on to the next unsolved question
+history                List the past submissions of the question
+help                   Show this help
+exit                   Leave the shell";
+
+/// Question of the open solution, fetched once
+struct Context {
+    question: Question,
+    meta_data: MetaData,
+}
+
+/// # Shell session
+/// One authenticated client and the solution being worked on. The
+/// metadata of the question is fetched once and reused by every command.
+struct Session<'a> {
+    lc: &'a LeetCode<Authorized>,
+    config: &'a Config,
+    code: Option<CodeFile>,
+    context: Option<Context>,
+    /// Problem list, fetched the first time `next` is used
+    problems: Option<Vec<Problem>>,
+}
+
+/// Reads commands until `exit` or end of input
+pub fn run(lc: &LeetCode<Authorized>, config: &Config, file: Option<String>) -> ExitCode {
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(e) => {
+            println!("Failed to start the shell : {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let history = dirs::data_dir().map(|dir| dir.join(HISTORY_FILE));
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    let mut session = Session {
+        lc,
+        config,
+        code: None,
+        context: None,
+        problems: None,
+    };
+    if let Some(file) = file {
+        if let Err(e) = session.open(&file) {
+            println!("{}", e.red());
+        }
+    }
+    println!("Type help to list the commands, exit to leave");
+
+    loop {
+        let line = match editor.readline(&session.prompt()) {
+            Ok(line) => line,
+            // Ctrl-C only drops the line being typed
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                println!("{e}");
+                break;
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        let result = match command {
+            "open" => match args.first() {
+                Some(target) => session.open(target),
+                None => Err("Usage : open <FILE|QUESTION>".to_string()),
+            },
+            "show" => session.show(),
+            "run" => session.run(args.first().copied()),
+            "submit" => session.submit(args.contains(&"--force"), args.contains(&"-y")),
+            "add-case" => session.add_case(&mut editor),
+            "next" => session.next(),
+            "history" => session.history(),
+            "help" => {
+                println!("{}", HELP);
+                Ok(())
+            }
+            "exit" | "quit" => break,
+            unknown => Err(format!(
+                "Unknown command {}, type help to list the commands",
+                unknown
+            )),
+        };
+        if let Err(e) = result {
+            println!("{}", e.red());
+        }
+    }
+
+    if let Some(history) = &history {
+        if let Some(dir) = history.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = editor.save_history(history);
+    }
+    ExitCode::SUCCESS
+}
+
+impl Session<'_> {
+    fn prompt(&self) -> String {
+        match &self.code {
+            Some(code) => format!("{}> ", code.question_title),
+            None => "leetcode> ".to_string(),
+        }
+    }
+
+    /// Opens the file, or the file of the question as named by the
+    /// file template, created with the starting code if missing
+    fn open(&mut self, target: &str) -> Result<(), String> {
+        let path = if Path::new(target).is_file() {
+            PathBuf::from(target)
+        } else {
+            let details = self
+                .lc
+                .question_details(target)
+                .map_err(|_| format!("{} is neither a file nor a question", target))?;
+            let language = self.config.language().unwrap_or_default();
+            let path = PathBuf::from(self.config.file_name(target, &language));
+            let snippet = details.snippet(&language).unwrap_or_default();
+            let region = self.config.region.unwrap_or_default();
+            CodeFile::scaffold(&path, &language, region, target, snippet)?;
+            path
+        };
+        let code = CodeFile::try_from_file(&path)?;
+        println!("Opened {} ({})", path.display(), code.question_title);
+        self.code = Some(code);
+        Ok(())
+    }
+
+    /// Reads the solution again, and fetches its question
+    /// unless it is the one fetched before
+    fn current(&mut self) -> Result<(&CodeFile, &Context), String> {
+        let Some(code) = &self.code else {
+            return Err(NO_SOLUTION.to_string());
+        };
+        let code = code.reload()?;
+        let fetched = matches!(
+            &self.context,
+            Some(context) if context.question.titleSlug == code.question_title
+        );
+        if !fetched {
+            let question = self.lc.question_metadata(&code.question_title)?;
+            let meta_data = self.lc.meta_data(&question);
+            self.context = Some(Context {
+                question,
+                meta_data,
+            });
+        }
+        self.code = Some(code);
+        match (&self.code, &self.context) {
+            (Some(code), Some(context)) => Ok((code, context)),
+            _ => Err(NO_SOLUTION.to_string()),
+        }
+    }
+
+    fn show(&self) -> Result<(), String> {
+        let Some(code) = &self.code else {
+            return Err(NO_SOLUTION.to_string());
+        };
+        let content = self.lc.question_content(&code.question_title)?.content;
+        println!("\n{}\n", html2md::parse_html(&content).trim());
+        Ok(())
+    }
+
+    fn run(&mut self, testcase: Option<&str>) -> Result<(), String> {
+        let (lc, config) = (self.lc, self.config);
+        let data_input = commands::read_testcases(&testcase.map(String::from))?;
+        let (code, context) = self.current()?;
+        let testcases = context
+            .question
+            .testcases(&context.meta_data, &data_input)?;
+        let run: Run = (
+            lc.execute_testcases(
+                code,
+                &context.question.questionId,
+                &context.meta_data,
+                &testcases,
+            ),
+            None,
+        );
+        commands::display_run(
+            &run,
+            code,
+            config.output.verbose.unwrap_or_default(),
+            config.output.format.unwrap_or_default(),
+        );
+        Ok(())
+    }
+
+    fn submit(&mut self, force: bool, yes: bool) -> Result<(), String> {
+        let (lc, config) = (self.lc, self.config);
+        let policy = if force {
+            SubmitPolicy::Always
+        } else {
+            config.submit.policy.unwrap_or_default()
+        };
+        let verbose = config.output.verbose.unwrap_or_default();
+        let format = config.output.format.unwrap_or_default();
+        let (code, context) = self.current()?;
+
+        let run = if policy.runs_testcases() {
+            let testcases = context.question.testcases(&context.meta_data, "")?;
+            let run: Run = (
+                lc.execute_testcases(
+                    code,
+                    &context.question.questionId,
+                    &context.meta_data,
+                    &testcases,
+                ),
+                None,
+            );
+            // in JSON mode the run is printed along with the submission
+            commands::review_run(&run, code, verbose, format);
+            Some(run.0)
+        } else {
+            None
+        };
+        if !policy.should_submit(run.as_ref()) {
+            return Err(
+                "Not submitting as the testcases didn't pass, use submit --force to submit anyway"
+                    .to_string(),
+            );
+        }
+        if !yes && config.submit.confirm.unwrap_or(true) {
+            println!("\n{}\n", code.preview());
+            if !commands::confirm("Submit this code?") {
+                println!("{}", "Submission cancelled".yellow().bold());
+                return Ok(());
+            }
+        }
+        let result = lc.submit_question(code, &context.question.questionId);
+        commands::display_submission(lc, run.as_ref(), result, code, format);
+        Ok(())
+    }
+
+    /// Asks for every parameter of the question and saves the testcase
+    fn add_case(&mut self, editor: &mut DefaultEditor) -> Result<(), String> {
+        let (code, context) = self.current()?;
+        let mut lines = vec![];
+        for name in context.meta_data.param_names() {
+            let line = editor
+                .readline(&format!("{} = ", name))
+                .map_err(|_| "Testcase not added".to_string())?;
+            lines.push(line.trim().to_string());
+        }
+        let testcases = Testcases::from_input(&lines.join("\n"), &context.meta_data)?;
+        testcases.save_custom(&code.question_title, &context.meta_data)?;
+        println!(
+            "Saved the testcase to {}, it runs by default from now on",
+            Testcases::custom_path(&code.question_title).display()
+        );
+        Ok(())
+    }
+
+    /// Opens the first unsolved question after the current one
+    fn next(&mut self) -> Result<(), String> {
+        if self.problems.is_none() {
+            println!("Fetching problems...");
+            self.problems = Some(self.lc.problems()?.sorted());
+        }
+        let problems = self.problems.as_deref().unwrap_or_default();
+        let current = self.code.as_ref().and_then(|code| {
+            problems
+                .iter()
+                .position(|problem| problem.stat.title_slug == code.question_title)
+        });
+        let start = current.map_or(0, |idx| idx + 1);
+        let Some(next) = problems[start..]
+            .iter()
+            .find(|problem| !problem.is_solved() && !problem.paid_only)
+        else {
+            return Err("No unsolved question left after this one".to_string());
+        };
+        let title_slug = next.stat.title_slug.clone();
+        self.open(&title_slug)
+    }
+
+    fn history(&self) -> Result<(), String> {
+        let Some(code) = &self.code else {
+            return Err(NO_SOLUTION.to_string());
+        };
+        self.lc
+            .submissions(Some(&code.question_title), 0, 20, "")?
+            .display();
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+use colored::Colorize;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::testcases::NamedInput;
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use leetcode_runner_cli::leetcode_api::worker::ExecutionResult;
+
+/// # Solution comparison
+/// Results of several solutions of the same question
+/// run against the same set of testcases
+pub struct Comparison {
+    pub inputs: Vec<NamedInput>,
+    pub entries: Vec<(CodeFile, ExecutionResult)>,
+}
+
+/// Runs every solution against `data_input`, or against the default
+/// testcases of the question when no input is given
+pub fn compare(
+    lc: &LeetCode<Authorized>,
+    codefiles: Vec<CodeFile>,
+    data_input: &str,
+) -> Result<Comparison, String> {
+    let Some(first) = codefiles.first() else {
+        return Err("No solutions to compare".to_string());
+    };
+    let question_title = first.question_title.clone();
+    if let Some(other) = codefiles
+        .iter()
+        .find(|codefile| codefile.question_title != question_title)
+    {
+        return Err(format!(
+            "{} solves {} while {} solves {}",
+            other.path.display(),
+            other.question_title,
+            first.path.display(),
+            question_title
+        ));
+    }
+
+    let ques = lc.question_metadata(&question_title)?;
+    let meta_data = lc.meta_data(&ques);
+    let testcases = ques.testcases(&meta_data, data_input)?;
+    let mut entries = vec![];
+    for codefile in codefiles {
+        println!("Running {}", codefile.path.display());
+        let result = lc.execute_testcases(&codefile, &ques.questionId, &meta_data, &testcases)?;
+        entries.push((codefile, result));
+    }
+    Ok(Comparison {
+        inputs: testcases.named_inputs(&meta_data),
+        entries,
+    })
+}
+
+impl Comparison {
+    /// Output of every solution for the case, `None` for
+    /// solutions that didn't run or gave no output
+    fn outputs(&self, case: usize) -> Vec<Option<&String>> {
+        self.entries
+            .iter()
+            .map(|(_, result)| match result {
+                ExecutionResult::Success(success) => success
+                    .cases
+                    .get(case)
+                    .and_then(|case| case.output.as_ref()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Cases the solutions give different outputs for,
+    /// solutions that didn't run at all are left out
+    pub fn disagreements(&self) -> Vec<usize> {
+        (0..self.inputs.len())
+            .filter(|&case| {
+                let outputs: Vec<Option<&String>> = self
+                    .entries
+                    .iter()
+                    .zip(self.outputs(case))
+                    .filter(|((_, result), _)| matches!(result, ExecutionResult::Success(_)))
+                    .map(|(_, output)| output)
+                    .collect();
+                outputs.iter().any(|output| *output != outputs[0])
+            })
+            .collect()
+    }
+
+    pub fn display(&self) {
+        println!("{}", self);
+    }
+}
+
+fn failure_label(result: &ExecutionResult) -> &str {
+    match result {
+        ExecutionResult::Success(_) => "",
+        ExecutionResult::CompileError(_) => "Compile Error",
+        ExecutionResult::RuntimeError(_) => "Runtime Error",
+        ExecutionResult::LimitExceeded(limit_exceeded) => &limit_exceeded.status_msg,
+        ExecutionResult::PendingResult(_) => "Pending",
+        ExecutionResult::Unknown(_) => "Unknown Error",
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(codefile, _)| {
+                codefile
+                    .path
+                    .file_name()
+                    .map_or(codefile.path.display().to_string(), |name| {
+                        name.to_string_lossy().to_string()
+                    })
+            })
+            .collect();
+        let width = names
+            .iter()
+            .map(String::len)
+            .chain(
+                self.entries
+                    .iter()
+                    .map(|(_, result)| failure_label(result).len()),
+            )
+            .max()
+            .unwrap_or_default()
+            .max(10)
+            + 2;
+
+        write!(f, "{:<10}", "Case")?;
+        for name in &names {
+            write!(f, "{:<width$}", name)?;
+        }
+        writeln!(f, "Agree")?;
+
+        let disagreements = self.disagreements();
+        for case in 0..self.inputs.len() {
+            write!(f, "{:<10}", case + 1)?;
+            for (_, result) in &self.entries {
+                let cell = match result {
+                    ExecutionResult::Success(success) => match success.cases.get(case) {
+                        Some(case) if case.is_correct() => format!("{:<width$}", "✓").green(),
+                        Some(case) if case.output.is_none() => {
+                            format!("{:<width$}", "no output").red()
+                        }
+                        _ => format!("{:<width$}", "✗").red(),
+                    },
+                    _ => format!("{:<width$}", "-").normal(),
+                };
+                write!(f, "{}", cell)?;
+            }
+            if disagreements.contains(&case) {
+                writeln!(f, "{}", "no".red().bold())?;
+            } else {
+                writeln!(f, "{}", "yes".green())?;
+            }
+        }
+
+        write!(f, "{:<10}", "Runtime")?;
+        for (_, result) in &self.entries {
+            match result {
+                ExecutionResult::Success(success) => write!(
+                    f,
+                    "{}",
+                    format!("{:<width$}", success.status_runtime).cyan()
+                )?,
+                result => write!(f, "{}", format!("{:<width$}", failure_label(result)).red())?,
+            }
+        }
+        write!(f, "\n{:<10}", "Memory")?;
+        for (_, result) in &self.entries {
+            match result {
+                ExecutionResult::Success(success) => {
+                    write!(f, "{}", format!("{:<width$}", success.status_memory).cyan())?
+                }
+                _ => write!(f, "{:<width$}", "-")?,
+            }
+        }
+
+        for &case in &disagreements {
+            write!(f, "\n\n{}", format!("Case {} :", case + 1).yellow().bold())?;
+            for (name, value) in &self.inputs[case] {
+                write!(f, "\n  {} = {}", name, value)?;
+            }
+            for (name, output) in names.iter().zip(self.outputs(case)) {
+                let output = output.map_or("no output", String::as_str);
+                write!(f, "\n  {:<width$}: {}", name, output)?;
+            }
+        }
+
+        if disagreements.is_empty() {
+            write!(
+                f,
+                "\n\n{}",
+                "All solutions agree on every case".green().bold()
+            )
+        } else {
+            write!(
+                f,
+                "\n\n{}",
+                format!("Solutions disagree on {} cases", disagreements.len())
+                    .red()
+                    .bold()
+            )
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use leetcode_runner_cli::file_parser::language::Language;
+use leetcode_runner_cli::leetcode_api::leetcode::{Region, Timing};
+use leetcode_runner_cli::leetcode_api::policy::SubmitPolicy;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Config of the project, looked up from the current directory upwards
+pub const PROJECT_CONFIG_FILE: &str = ".leetcode.toml";
+/// Config of the user, inside the user config directory
+pub const USER_CONFIG_FILE: &str = "leetcode-runner-cli/config.toml";
+/// Default name of downloaded solutions
+pub const DEFAULT_FILE_TEMPLATE: &str = "{slug}.{ext}";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// # Configuration
+/// Defaults for the options of every command. The user config is
+/// loaded first, the project config overrides what it sets, and
+/// options given on the command line override both.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Language of the solution picked from the current directory,
+    /// either its name on leetcode or its extension
+    pub language: Option<String>,
+    /// Name of downloaded solutions, `{slug}` and `{ext}` are
+    /// replaced with the question and the extension of the language
+    pub file_template: Option<String>,
+    pub region: Option<Region>,
+    pub output: OutputConfig,
+    pub poll: PollConfig,
+    pub submit: SubmitConfig,
+    pub watch: WatchConfig,
+    pub local: LocalConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub format: Option<OutputFormat>,
+    pub colors: Option<bool>,
+    /// Show the input of passing testcases too
+    pub verbose: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PollConfig {
+    /// Delay between two checks of a pending result, in milliseconds
+    pub interval_ms: Option<u64>,
+    pub rate_limit_retries: Option<u32>,
+    /// Wait before the first retry of a rate limited run, in seconds
+    pub rate_limit_backoff_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubmitConfig {
+    /// `when-correct`, `always` or `skip-run`
+    pub policy: Option<SubmitPolicy>,
+    /// Show the code and ask before submitting
+    pub confirm: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchConfig {
+    /// Time without changes to wait for before running again, in milliseconds
+    pub debounce_ms: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LocalConfig {
+    /// Time the program may run for before it gets killed, in seconds
+    pub timeout_secs: Option<u64>,
+}
+
+impl Config {
+    /// Loads the user config and the project config, missing files are skipped
+    pub fn load() -> Result<Self, String> {
+        let mut config = Config::default();
+        if let Some(path) = Self::user_path() {
+            config.merge(Self::from_file(&path)?);
+        }
+        if let Some(path) = Self::project_path() {
+            config.merge(Self::from_file(&path)?);
+        }
+        if let Some(language) = &config.language {
+            if parse_language(language).is_none() {
+                return Err(format!("Unsupported language {} in config", language));
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn user_path() -> Option<PathBuf> {
+        let path = dirs::config_dir()?.join(USER_CONFIG_FILE);
+        path.is_file().then_some(path)
+    }
+
+    /// Nearest `.leetcode.toml` from the current directory upwards
+    pub fn project_path() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        cwd.ancestors()
+            .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let config = std::fs::read_to_string(path)
+            .map_err(|_| format!("Failed to read config {}", path.display()))?;
+        toml::from_str(&config).map_err(|e| format!("Invalid config {} : {}", path.display(), e))
+    }
+
+    /// Overrides the options `other` sets
+    pub fn merge(&mut self, other: Config) {
+        self.language = other.language.or(self.language.take());
+        self.file_template = other.file_template.or(self.file_template.take());
+        self.region = other.region.or(self.region);
+        self.output.format = other.output.format.or(self.output.format);
+        self.output.colors = other.output.colors.or(self.output.colors);
+        self.output.verbose = other.output.verbose.or(self.output.verbose);
+        self.poll.interval_ms = other.poll.interval_ms.or(self.poll.interval_ms);
+        self.poll.rate_limit_retries = other
+            .poll
+            .rate_limit_retries
+            .or(self.poll.rate_limit_retries);
+        self.poll.rate_limit_backoff_secs = other
+            .poll
+            .rate_limit_backoff_secs
+            .or(self.poll.rate_limit_backoff_secs);
+        self.submit.policy = other.submit.policy.or(self.submit.policy);
+        self.submit.confirm = other.submit.confirm.or(self.submit.confirm);
+        self.watch.debounce_ms = other.watch.debounce_ms.or(self.watch.debounce_ms);
+        self.local.timeout_secs = other.local.timeout_secs.or(self.local.timeout_secs);
+    }
+
+    pub fn language(&self) -> Option<Language> {
+        self.language.as_deref().and_then(parse_language)
+    }
+
+    /// Name of a downloaded solution of the question
+    pub fn file_name(&self, title_slug: &str, language: &Language) -> String {
+        self.file_template
+            .as_deref()
+            .unwrap_or(DEFAULT_FILE_TEMPLATE)
+            .replace("{slug}", title_slug)
+            .replace("{ext}", language.extension())
+    }
+
+    pub fn timing(&self) -> Timing {
+        let mut timing = Timing::default();
+        if let Some(interval_ms) = self.poll.interval_ms {
+            timing.poll_interval = Duration::from_millis(interval_ms);
+        }
+        if let Some(retries) = self.poll.rate_limit_retries {
+            timing.rate_limit_retries = retries;
+        }
+        if let Some(backoff) = self.poll.rate_limit_backoff_secs {
+            timing.rate_limit_backoff = Duration::from_secs(backoff);
+        }
+        timing
+    }
+}
+
+/// Language by its name on leetcode, or by its extension
+pub(crate) fn parse_language(language: &str) -> Option<Language> {
+    Language::from_name(language).or_else(|| Language::from_extension(language))
+}
@@ -0,0 +1,209 @@
+use colored::Colorize;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::language::Language;
+use leetcode_runner_cli::leetcode_api::history::{SubmissionCode, SubmissionSummary};
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode, Region};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File of the export directory recording what has been exported
+const MANIFEST_FILE: &str = ".export.json";
+/// Statement and metadata of the question, next to the solution
+const README_FILE: &str = "README.md";
+/// Wait between the questions, to stay clear of leetcode's rate limit
+const REQUEST_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// Newest submission looked at, older ones are skipped on later runs
+    last_submission: u64,
+    /// Exported submission of every question, by title slug
+    questions: BTreeMap<String, Exported>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Exported {
+    submission: u64,
+    /// Solution file, relative to the export directory
+    solution: String,
+}
+
+/// # Export summary
+/// Questions written by an export run, and the ones left out
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    pub exported: Vec<String>,
+    /// Questions solved in languages there is no file extension for
+    pub unsupported: Vec<(String, String)>,
+    /// Questions which failed to export, retried on the next run
+    pub failed: Vec<(String, String)>,
+}
+
+impl ExportSummary {
+    pub fn display(&self) {
+        println!("{}", self);
+    }
+}
+
+/// Writes the latest accepted solution of every question into
+/// `<dir>/<id>-<question>/`, as `solution.<extension>` along with a
+/// `README.md` holding the metadata and the statement of the question.
+///
+/// Only submissions newer than the ones of the previous export are
+/// looked at, so later runs just add what has been solved since.
+/// Links point to the site of `region`.
+pub fn export(
+    lc: &LeetCode<Authorized>,
+    dir: &Path,
+    region: Region,
+) -> Result<ExportSummary, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|_| format!("Failed to create directory {}", dir.display()))?;
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let mut manifest: Manifest = match std::fs::read_to_string(&manifest_path) {
+        Ok(manifest) => serde_json::from_str(&manifest)
+            .map_err(|_| format!("Failed to parse {}", manifest_path.display()))?,
+        Err(_) => Manifest::default(),
+    };
+
+    let accepted = lc.accepted_submissions(manifest.last_submission)?;
+    let newest = accepted.first().map(|submission| submission.id);
+    let mut summary = ExportSummary::default();
+    for submission in accepted {
+        let title_slug = submission.title_slug.clone();
+        if manifest
+            .questions
+            .get(&title_slug)
+            .is_some_and(|exported| exported.submission == submission.id)
+        {
+            continue;
+        }
+        let Some(language) = Language::from_name(&submission.lang) else {
+            summary.unsupported.push((title_slug, submission.lang));
+            continue;
+        };
+        println!("Exporting {}", title_slug);
+        match export_question(lc, dir, submission, &language, region) {
+            Ok(exported) => {
+                if let Some(previous) = manifest.questions.get(&title_slug) {
+                    // solved in another language since the previous export
+                    if previous.solution != exported.solution {
+                        let _ = std::fs::remove_file(dir.join(&previous.solution));
+                    }
+                }
+                manifest.questions.insert(title_slug.clone(), exported);
+                summary.exported.push(title_slug);
+            }
+            Err(e) => summary.failed.push((title_slug, e)),
+        }
+        std::thread::sleep(REQUEST_DELAY);
+    }
+
+    // failed questions have to be looked at again on the next run
+    if summary.failed.is_empty() {
+        if let Some(newest) = newest {
+            manifest.last_submission = newest;
+        }
+    }
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|_| "Failed to serialize export manifest".to_string())?;
+    std::fs::write(&manifest_path, manifest_json)
+        .map_err(|_| format!("Failed to write {}", manifest_path.display()))?;
+    Ok(summary)
+}
+
+fn export_question(
+    lc: &LeetCode<Authorized>,
+    dir: &Path,
+    submission: SubmissionSummary,
+    language: &Language,
+    region: Region,
+) -> Result<Exported, String> {
+    let question = lc.question_details(&submission.title_slug)?;
+    // the listing doesn't have the percentiles
+    let submission = match lc.submission_code(submission.id) {
+        Ok(details) => details,
+        Err(_) => SubmissionCode::from(submission),
+    };
+
+    let folder = match question.questionFrontendId.parse::<u32>() {
+        Ok(id) => format!("{:04}-{}", id, question.titleSlug),
+        Err(_) => format!("{}-{}", question.questionFrontendId, question.titleSlug),
+    };
+    let folder_path = dir.join(&folder);
+    std::fs::create_dir_all(&folder_path)
+        .map_err(|_| format!("Failed to create directory {}", folder_path.display()))?;
+
+    let solution = PathBuf::from(&folder).join(format!("solution.{}", language.extension()));
+    let code = CodeFile::template(language, region, &question.titleSlug, &submission.code);
+    write(&dir.join(&solution), &code)?;
+
+    let tags: Vec<&str> = question
+        .topicTags
+        .iter()
+        .map(|tag| tag.name.as_str())
+        .collect();
+    let readme = format!(
+        "# {}. {}\n\n\
+        | Difficulty | Tags | Language | Runtime | Memory |\n\
+        | --- | --- | --- | --- | --- |\n\
+        | {} | {} | {} | {} | {} |\n\n\
+        {}\n\n\
+        {}\n",
+        question.questionFrontendId,
+        question.title,
+        question.difficulty,
+        tags.join(", "),
+        language,
+        with_percentile(&submission.runtime, submission.runtime_percentile),
+        with_percentile(&submission.memory, submission.memory_percentile),
+        region.problem_url(&question.titleSlug),
+        question
+            .content
+            .map(|content| html2md::parse_html(&content))
+            .unwrap_or_default()
+            .trim_end(),
+    );
+    write(&folder_path.join(README_FILE), &readme)?;
+
+    Ok(Exported {
+        submission: submission.id,
+        solution: solution.display().to_string(),
+    })
+}
+
+fn with_percentile(value: &str, percentile: Option<f64>) -> String {
+    match percentile {
+        Some(percentile) => format!("{} (beats {:.2}%)", value, percentile),
+        None => value.to_string(),
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|_| format!("Failed to write {}", path.display()))
+}
+
+impl std::fmt::Display for ExportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exported.is_empty() {
+            write!(f, "{}", "Nothing new to export".green().bold())?;
+        } else {
+            write!(
+                f,
+                "{}",
+                format!("Exported {} solutions", self.exported.len())
+                    .green()
+                    .bold()
+            )?;
+        }
+        for (title_slug, lang) in &self.unsupported {
+            write!(f, "\nSkipped {} : {} is not supported", title_slug, lang)?;
+        }
+        for (title_slug, error) in &self.failed {
+            write!(f, "\n{} {} : {}", "Failed".red(), title_slug, error)?;
+        }
+        Ok(())
+    }
+}
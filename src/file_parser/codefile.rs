@@ -1,14 +1,20 @@
 use super::language::*;
-use std::{
-    io::{Read, Write},
-    path::PathBuf,
-};
+use crate::leetcode_api::leetcode::Region;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
 
+/// # Code file
+/// Solution file of a question. The question is told by the problem
+/// link in the file, the code sent to leetcode is the part between the
+/// `#LCSTART` and `#LCEND` comments, the whole file if they're missing.
 pub struct CodeFile {
     pub language: Language,
     pub path: std::path::PathBuf,
     pub question_title: String,
     pub code: String,
+    /// Lines of the file before the extracted code,
+    /// line `n` of the code is line `line_offset + n` of the file
+    pub line_offset: usize,
 }
 
 impl Default for CodeFile {
@@ -18,14 +24,37 @@ impl Default for CodeFile {
             path: PathBuf::from("main.rs"),
             question_title: Default::default(),
             code: Default::default(),
+            line_offset: Default::default(),
         }
     }
 }
 
 impl CodeFile {
-    pub fn from_dir() -> Self {
-        let mut code_file: Option<CodeFile> = None;
-        let files = std::fs::read_dir("./").unwrap();
+    /// Picks the code file from the current directory, files named
+    /// `main` first, then the ones named after `file_template`, which
+    /// are also looked for in the directory of the template. Only files
+    /// of `language` are picked, when given. `None` when there is no
+    /// such file.
+    pub fn from_dir(
+        language: Option<&Language>,
+        file_template: Option<&str>,
+    ) -> Result<Option<Self>, String> {
+        let template = file_template.map(Path::new);
+        let mut files: Vec<_> = std::fs::read_dir("./")
+            .map_err(|_| "Failed to read the current directory".to_string())?
+            .collect();
+        let template_dir = template
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty());
+        if let Some(files_of_template) = template_dir.and_then(|dir| std::fs::read_dir(dir).ok()) {
+            files.extend(files_of_template);
+        }
+        let name_template = template
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str());
+
+        // the lowest rank is picked : main, named after the template, any other
+        let mut code_file: Option<(u8, CodeFile)> = None;
         for file in files {
             let Ok(file) = file else {
                 // Bad path
@@ -33,59 +62,88 @@ impl CodeFile {
             };
             let path = file.path();
             let Some(valid_file) = Self::is_valid_file(&path) else {continue};
+            if language.is_some_and(|language| *language != valid_file.1.language) {
+                continue;
+            }
             let file_name = valid_file.0;
-            code_file = Some(valid_file.1);
+            let extension = valid_file.1.language.extension();
+            let rank = if file_name.starts_with("main") {
+                0
+            } else if name_template
+                .is_some_and(|template| Self::matches_template(file_name, template, extension))
+            {
+                1
+            } else {
+                2
+            };
+            if code_file.as_ref().is_some_and(|(picked, _)| *picked < rank) {
+                continue;
+            }
+            code_file = Some((rank, valid_file.1));
 
-            if file_name.starts_with("main") {
+            if rank == 0 {
                 break;
             }
         }
-        let mut code_file = code_file.unwrap_or_else(|| {
-            let default_code_file: CodeFile = Default::default();
-            println!(
-                "No code file found. Creating a new file named {}",
-                default_code_file.path.display()
-            );
-            let mut file =
-                std::fs::File::create(&default_code_file.path).expect("Error during file creation");
-            let two_sum_problem = b"struct Solution;\n\n// https://leetcode.com/problems/two-sum/ #LCSTART\n\nimpl Solution {\n\tpub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {\n\n\t}\n} // #LCEND\nfn main() {}";
-            file.write_all(two_sum_problem).expect("File write failed");
-
-            default_code_file
-        });
-        let mut file = std::fs::File::open(&code_file.path).unwrap();
-        let mut code = String::new();
-        file.read_to_string(&mut code)
-            .expect(&format!("Failed to read file {}", code_file.path.display()));
-        let parsed_file = Self::parse_code(&code);
-        let Ok((question_title, parsed_code)) = parsed_file else{
-            eprintln!("{}", parsed_file.err().unwrap());
-            std::process::exit(1);
+        code_file.map(|(_, code_file)| code_file.reload()).transpose()
+    }
+
+    /// Whether the file name is one the template gives to some question,
+    /// `{slug}` standing for any question title
+    fn matches_template(file_name: &str, template: &str, extension: &str) -> bool {
+        let template = template.replace("{ext}", extension);
+        match template.split_once("{slug}") {
+            Some((prefix, suffix)) => {
+                file_name.len() > prefix.len() + suffix.len()
+                    && file_name.starts_with(prefix)
+                    && file_name.ends_with(suffix)
+            }
+            None => file_name == template,
+        }
+    }
+
+    /// Writes a `main` file holding the two-sum problem, to start from
+    /// when there is no code file yet
+    pub fn create_default(language: Option<&Language>, region: Region) -> Result<Self, String> {
+        let mut default_code_file: CodeFile = Default::default();
+        if let Some(language) = language {
+            default_code_file.language = language.clone();
+            default_code_file.path = PathBuf::from(format!("main.{}", language.extension()));
+        }
+        let two_sum_problem = match &default_code_file.language {
+            Language::Rust => Self::template(
+                &Language::Rust,
+                region,
+                "two-sum",
+                "impl Solution {\n\tpub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {\n\n\t}\n}",
+            ),
+            language => Self::template(language, region, "two-sum", ""),
         };
-        code_file.question_title = question_title;
-        code_file.code = parsed_code;
-        code_file
+        std::fs::write(&default_code_file.path, two_sum_problem)
+            .map_err(|_| format!("Failed to write {}", default_code_file.path.display()))?;
+        default_code_file.reload()
     }
 
-    fn is_valid_file<'a>(path: &'a std::path::PathBuf) -> Option<(&'a str, Self)> {
+    fn is_valid_file(path: &std::path::Path) -> Option<(&str, Self)> {
         let file_name = path.file_name().and_then(|filename| filename.to_str())?;
         let extension = path.extension().and_then(|ext| ext.to_str())?;
-        let language = Language::from_str(extension)?;
+        let language = Language::from_extension(extension)?;
 
         Some((
             file_name,
             CodeFile {
                 language,
-                path: path.clone(),
+                path: path.to_path_buf(),
                 question_title: String::new(),
                 code: String::new(),
+                line_offset: 0,
             },
         ))
     }
 
-    fn parse_code(code: &str) -> Result<(String, String), String> {
-        let question_title: String;
-        let parsed_code: String;
+    /// Extracts the question title, the code between the delimiters
+    /// and the number of lines before the extracted code
+    fn parse_code(code: &str) -> Result<(String, String, usize), String> {
         let start = code
             .find("#LCSTART")
             .map(|idx| idx + code[idx..].find('\n').unwrap_or(0))
@@ -95,37 +153,104 @@ impl CodeFile {
             .unwrap_or(0);
 
         let end = code.find("#LCEND").unwrap_or(code.len());
-        if let Some(problem) = code.find("leetcode.com/problems/") {
-            let problem = (&code[problem..]).split_whitespace().next().unwrap();
-            let problem = problem.split('/').skip(2).next().unwrap();
-            question_title = problem.to_string();
-        } else {
+        let problem = code
+            .find("leetcode.com/problems/")
+            .or_else(|| code.find("leetcode.cn/problems/"));
+        let Some(problem) = problem else {
             return Err("No leetcode problem found in the code file. Please add the problem link in the code file using comments.".to_string());
+        };
+        let problem = code[problem..].split_whitespace().next().unwrap();
+        let question_title = problem.split('/').nth(2).unwrap().to_string();
+        let parsed_code = code[start..end].to_string();
+        let line_offset = code[..start].matches('\n').count();
+
+        Ok((question_title, parsed_code, line_offset))
+    }
+
+    /// Contents of a code file holding the given code, with the problem
+    /// link of the region and the delimiters placed the way `parse_code`
+    /// expects them
+    pub fn template(
+        language: &Language,
+        region: Region,
+        question_title: &str,
+        code: &str,
+    ) -> String {
+        let comment = language.comment();
+        let code = code.trim_end();
+        let link = region.problem_url(question_title);
+        let snippet = format!("{comment} {link} #LCSTART\n\n{code}\n\n{comment} #LCEND\n");
+        match language {
+            Language::Rust => format!("struct Solution;\n\n{snippet}\nfn main() {{}}\n"),
+            _ => snippet,
         }
-        parsed_code = code[start..end].to_string();
+    }
 
-        Ok((question_title, parsed_code))
+    /// Writes a new code file of the question holding its starting
+    /// code, an existing file is left as is
+    pub fn scaffold(
+        path: &std::path::Path,
+        language: &Language,
+        region: Region,
+        question_title: &str,
+        snippet: &str,
+    ) -> Result<(), String> {
+        if path.exists() {
+            return Ok(());
+        }
+        std::fs::write(path, Self::template(language, region, question_title, snippet))
+            .map_err(|_| format!("Failed to write {}", path.display()))
     }
 
-    pub fn from_file(path: String) -> Self {
-        let path = PathBuf::from(path);
-        let (_, mut valid_file) =
-            Self::is_valid_file(&path).expect("Improper filename or the language is not supported");
-        let file = std::fs::File::open(&path);
-        let Ok(mut file) = file else {
-            eprintln!("Error while opening file {}", path.display());
-            std::process::exit(1);
-        };
-        let mut code = String::new();
-        file.read_to_string(&mut code)
-            .expect(&format!("Failed to read file {}", path.display()));
-        let parsed_file = Self::parse_code(&code);
-        let Ok((question_title, parsed_code)) = parsed_file else{
-            eprintln!("{}", parsed_file.err().unwrap());
-            std::process::exit(1);
+    /// Line of the file the given line of the extracted code is at
+    pub fn file_line(&self, code_line: usize) -> usize {
+        self.line_offset + code_line
+    }
+
+    /// The code sent to leetcode, numbered with the lines of the
+    /// file it was extracted from, along with what was detected
+    pub fn preview(&self) -> String {
+        let lines: Vec<&str> = self.code.lines().collect();
+        let width = self.file_line(lines.len()).to_string().len();
+        let mut preview = format!(
+            "Problem  : {}\nLanguage : {}\nFile     : {}\nLines    : {} ({}-{} of the file)\n",
+            self.question_title.cyan(),
+            self.language.to_string().cyan(),
+            self.path.display(),
+            lines.len(),
+            self.file_line(1),
+            self.file_line(lines.len()),
+        );
+        for (idx, line) in lines.iter().enumerate() {
+            let number = format!("{:>width$} │", self.file_line(idx + 1));
+            preview.push_str(&format!("\n{} {}", number.dimmed(), line));
+        }
+        preview
+    }
+
+    /// Reads the file again, errors are returned rather than
+    /// exiting as the file may be halfway through an edit
+    pub fn reload(&self) -> Result<Self, String> {
+        let code = std::fs::read_to_string(&self.path)
+            .map_err(|_| format!("Failed to read file {}", self.path.display()))?;
+        let (question_title, code, line_offset) = Self::parse_code(&code)?;
+        Ok(CodeFile {
+            language: self.language.clone(),
+            path: self.path.clone(),
+            question_title,
+            code,
+            line_offset,
+        })
+    }
+
+    /// Reads the code file, its language is told by the extension
+    pub fn try_from_file(path: &std::path::Path) -> Result<Self, String> {
+        let Some((_, code_file)) = Self::is_valid_file(path) else {
+            return Err(format!(
+                "{} is not a file of a supported language",
+                path.display()
+            ));
         };
-        valid_file.question_title = question_title;
-        valid_file.code = parsed_code;
-        valid_file
+        code_file.reload()
     }
 }
@@ -1,4 +1,4 @@
-#[derive(Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub enum Language {
     #[default]
     Rust,
@@ -13,7 +13,8 @@ pub enum Language {
     Typescript,
 }
 impl Language {
-    pub fn from_str(input: &str) -> Option<Language> {
+    /// Language from the extension of its files, e.g. `py`
+    pub fn from_extension(input: &str) -> Option<Language> {
         match input {
             "rs" => Some(Language::Rust),
             "py" => Some(Language::Python3),
@@ -28,6 +29,42 @@ impl Language {
             _ => None,
         }
     }
+    /// Language from its name on leetcode, e.g. `python3`
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name {
+            "rust" => Some(Language::Rust),
+            "python3" => Some(Language::Python3),
+            "cpp" => Some(Language::Cpp),
+            "java" => Some(Language::Java),
+            "c" => Some(Language::C),
+            "javascript" => Some(Language::Javascript),
+            "golang" => Some(Language::Go),
+            "kotlin" => Some(Language::Kotlin),
+            "swift" => Some(Language::Swift),
+            "typescript" => Some(Language::Typescript),
+            _ => None,
+        }
+    }
+    pub fn comment(&self) -> &str {
+        match self {
+            Language::Python3 => "#",
+            _ => "//",
+        }
+    }
+    pub fn extension(&self) -> &str {
+        match self {
+            Language::Rust => "rs",
+            Language::Python3 => "py",
+            Language::Cpp => "cpp",
+            Language::Java => "java",
+            Language::C => "c",
+            Language::Javascript => "js",
+            Language::Go => "go",
+            Language::Kotlin => "kt",
+            Language::Swift => "swift",
+            Language::Typescript => "ts",
+        }
+    }
     pub fn to_str(&self) -> &str {
         match self {
             Language::Rust => "rust",
@@ -42,7 +79,10 @@ impl Language {
             Language::Typescript => "typescript",
         }
     }
-    pub fn to_string(&self) -> String {
-        self.to_str().to_string()
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
     }
 }
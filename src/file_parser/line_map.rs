@@ -0,0 +1,117 @@
+use super::codefile::CodeFile;
+
+/// # Map error lines to the code file
+/// Leetcode reports errors against the submitted code only, e.g.
+/// `Line 5: Char 9: error: ...` or `Line 5 in twoSum (Solution.py)`,
+/// which starts at `#LCSTART` rather than at the top of the file.
+/// These references are rewritten to `path:line:col` of the code file
+/// so editors can jump to them.
+pub fn remap_lines(codefile: &CodeFile, message: &str) -> String {
+    message
+        .lines()
+        .map(|line| remap_line(codefile, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn remap_line(codefile: &CodeFile, line: &str) -> String {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let Some(rest) = line.trim_start().strip_prefix("Line ") else {
+        return line.to_string();
+    };
+    let Some((line_no, rest)) = split_number(rest) else {
+        return line.to_string();
+    };
+    if refers_to_other_file(codefile, rest) {
+        return line.to_string();
+    }
+    let path = codefile.path.display();
+    let line_no = codefile.file_line(line_no);
+    if let Some((col, rest)) = rest.strip_prefix(": Char ").and_then(split_number) {
+        let rest = rest.strip_prefix(':').unwrap_or(rest);
+        return format!("{indent}{path}:{line_no}:{col}:{rest}");
+    }
+    let rest = rest.strip_prefix(':').unwrap_or(rest);
+    format!("{indent}{path}:{line_no}:{rest}")
+}
+
+/// Errors raised in the headers / libraries of the judge, e.g.
+/// `Line 1034: Char 9: runtime error: ... (stl_vector.h)`
+fn refers_to_other_file(codefile: &CodeFile, rest: &str) -> bool {
+    let Some(file) = rest
+        .trim_end()
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit('(').next())
+    else {
+        return false;
+    };
+    match file.rsplit_once('.') {
+        Some((_, extension)) => extension != codefile.language.extension(),
+        None => false,
+    }
+}
+
+fn split_number(text: &str) -> Option<(usize, &str)> {
+    let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+    let number = text[..digits].parse().ok()?;
+    Some((number, &text[digits..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_parser::language::Language;
+    use std::path::PathBuf;
+
+    fn codefile(language: Language, path: &str, line_offset: usize) -> CodeFile {
+        CodeFile {
+            language,
+            path: PathBuf::from(path),
+            line_offset,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn offsets_line_and_keeps_column() {
+        let codefile = codefile(Language::Cpp, "src/main.cpp", 12);
+        assert_eq!(
+            remap_lines(&codefile, "Line 5: Char 9: error: expected ';'"),
+            "src/main.cpp:17:9: error: expected ';'"
+        );
+    }
+
+    #[test]
+    fn offsets_line_without_column() {
+        let codefile = codefile(Language::Python3, "main.py", 3);
+        assert_eq!(
+            remap_lines(
+                &codefile,
+                "IndexError: list index out of range\n    Line 4 in twoSum (Solution.py)"
+            ),
+            "IndexError: list index out of range\n    main.py:7: in twoSum (Solution.py)"
+        );
+    }
+
+    #[test]
+    fn zero_offset_keeps_line() {
+        let codefile = codefile(Language::Rust, "main.rs", 0);
+        assert_eq!(
+            remap_lines(&codefile, "Line 1: Char 1: error[E0425]"),
+            "main.rs:1:1: error[E0425]"
+        );
+    }
+
+    #[test]
+    fn leaves_other_lines_alone() {
+        let codefile = codefile(Language::Cpp, "main.cpp", 12);
+        let message = [
+            "Line 1034: Char 9: runtime error: out of bounds (stl_vector.h)",
+            "Linear scan failed",
+            "Line x: Char 9: error",
+            "error: Line 5: Char 9",
+        ]
+        .join("\n");
+        assert_eq!(remap_lines(&codefile, &message), message);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod codefile;
-mod language;
+pub mod language;
+pub mod line_map;
+pub mod testcases;
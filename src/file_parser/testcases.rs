@@ -0,0 +1,208 @@
+use crate::leetcode_api::metadata::MetaData;
+use std::path::PathBuf;
+
+/// Directory holding the per-problem testcase directories,
+/// i.e. `testcases/<question_title>/`
+pub const TESTCASE_DIR: &str = "testcases";
+/// File inside the per-problem directory where failing inputs are saved
+pub const REGRESSIONS_FILE: &str = "regressions.txt";
+/// File inside the per-problem directory where testcases added by hand are saved
+pub const CUSTOM_FILE: &str = "custom.txt";
+/// Maximum number of testcases leetcode accepts in a single run
+pub const MAX_CASES_PER_RUN: usize = 10;
+
+/// Parameters of a testcase as `(name, value)` pairs
+pub type NamedInput = Vec<(String, String)>;
+
+/// # Testcase set
+/// Collection of testcases, each one holding the input lines
+/// of a single case joined by a newline (the same format as
+/// leetcode's `exampleTestcaseList`).
+#[derive(Debug, Default, Clone)]
+pub struct Testcases {
+    pub cases: Vec<String>,
+}
+
+impl Testcases {
+    /// Splits raw testcase input into cases of `lines_per_case` lines.
+    /// Blank lines are ignored. When `lines_per_case` is `None`
+    /// the whole input is treated as a single case.
+    pub fn parse(input: &str, lines_per_case: Option<usize>) -> Result<Self, String> {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let Some(lines_per_case) = lines_per_case else {
+            let cases = if lines.is_empty() {
+                vec![]
+            } else {
+                vec![lines.join("\n")]
+            };
+            return Ok(Self { cases });
+        };
+        if !lines.len().is_multiple_of(lines_per_case) {
+            return Err(format!(
+                "Testcase input has {} lines, which is not a multiple of {} lines per testcase",
+                lines.len(),
+                lines_per_case
+            ));
+        }
+        let cases = lines
+            .chunks(lines_per_case)
+            .map(|case| case.join("\n"))
+            .collect();
+        Ok(Self { cases })
+    }
+
+    /// Per-problem testcase directory, `testcases/<question_title>/`
+    pub fn problem_dir(question_title: &str) -> PathBuf {
+        PathBuf::from(TESTCASE_DIR).join(question_title)
+    }
+
+    /// Path of the saved regressions of the problem
+    pub fn regressions_path(question_title: &str) -> PathBuf {
+        Self::problem_dir(question_title).join(REGRESSIONS_FILE)
+    }
+
+    /// Path of the testcases of the problem added by hand
+    pub fn custom_path(question_title: &str) -> PathBuf {
+        Self::problem_dir(question_title).join(CUSTOM_FILE)
+    }
+
+    /// Path randomly generated testcases are written to by default.
+    /// Kept outside the per-problem directory so they aren't
+    /// picked up by default runs.
+    pub fn generated_path(question_title: &str) -> PathBuf {
+        PathBuf::from(TESTCASE_DIR).join(format!("{}.generated.txt", question_title))
+    }
+
+    /// Validates and splits the input into cases as per the metaData
+    pub fn from_input(input: &str, meta_data: &MetaData) -> Result<Self, String> {
+        meta_data.validate(input)?;
+        Self::parse(input, meta_data.lines_per_case())
+    }
+
+    /// Reads every testcase file from the per-problem directory
+    /// (saved regressions included). A missing directory is not an error.
+    pub fn from_problem_dir(question_title: &str, meta_data: &MetaData) -> Result<Self, String> {
+        let mut testcases = Self::default();
+        let Ok(files) = std::fs::read_dir(Self::problem_dir(question_title)) else {
+            return Ok(testcases);
+        };
+        let mut paths: Vec<PathBuf> = files
+            .filter_map(|file| file.ok().map(|file| file.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+        for path in paths {
+            let Ok(input) = std::fs::read_to_string(&path) else {
+                return Err(format!("Failed to read testcase file {}", path.display()));
+            };
+            let cases = Self::from_input(&input, meta_data)
+                .map_err(|e| format!("{} : {}", path.display(), e))?;
+            testcases.extend(cases);
+        }
+        Ok(testcases)
+    }
+
+    /// Appends cases which aren't already present
+    pub fn extend(&mut self, other: Testcases) {
+        for case in other.cases {
+            if !self.cases.contains(&case) {
+                self.cases.push(case);
+            }
+        }
+    }
+
+    /// Cases at the given indices
+    pub fn select(&self, indices: &[usize]) -> Self {
+        let cases = indices
+            .iter()
+            .filter_map(|&idx| self.cases.get(idx).cloned())
+            .collect();
+        Self { cases }
+    }
+
+    /// Appends the cases to the saved regressions of the problem,
+    /// skipping the ones already saved
+    pub fn save_regressions(
+        &self,
+        question_title: &str,
+        meta_data: &MetaData,
+    ) -> Result<(), String> {
+        self.append_to(
+            &Self::regressions_path(question_title),
+            question_title,
+            meta_data,
+        )
+    }
+
+    /// Appends the cases to the testcases of the problem added by hand,
+    /// skipping the ones already saved
+    pub fn save_custom(&self, question_title: &str, meta_data: &MetaData) -> Result<(), String> {
+        self.append_to(
+            &Self::custom_path(question_title),
+            question_title,
+            meta_data,
+        )
+    }
+
+    fn append_to(
+        &self,
+        path: &std::path::Path,
+        question_title: &str,
+        meta_data: &MetaData,
+    ) -> Result<(), String> {
+        let mut saved = match std::fs::read_to_string(path) {
+            Ok(input) => Self::from_input(&input, meta_data)
+                .map_err(|e| format!("{} : {}", path.display(), e))?,
+            Err(_) => Self::default(),
+        };
+        saved.extend(self.clone());
+        std::fs::create_dir_all(Self::problem_dir(question_title))
+            .map_err(|_| format!("Failed to create directory {}", TESTCASE_DIR))?;
+        std::fs::write(path, format!("{}\n", saved))
+            .map_err(|_| format!("Failed to save testcases to {}", path.display()))
+    }
+
+    /// Pairs the lines of every case with the parameter names of the metaData
+    pub fn named_inputs(&self, meta_data: &MetaData) -> Vec<NamedInput> {
+        let names = meta_data.param_names();
+        self.cases
+            .iter()
+            .map(|case| {
+                case.lines()
+                    .enumerate()
+                    .map(|(idx, value)| {
+                        let name = names.get(idx).copied().unwrap_or("input");
+                        (name.to_string(), value.to_string())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// Groups the cases into inputs of at most `MAX_CASES_PER_RUN` cases,
+    /// each one ready to be sent as `data_input` along with its number of cases
+    pub fn batches(&self) -> Vec<(String, usize)> {
+        self.cases
+            .chunks(MAX_CASES_PER_RUN)
+            .map(|batch| (batch.join("\n"), batch.len()))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Testcases {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cases.join("\n\n"))
+    }
+}
@@ -0,0 +1,259 @@
+use colored::Colorize;
+use serde_json::Value;
+
+/// Elements shown on each side of the first difference
+const WINDOW: usize = 3;
+
+/// # Output difference
+/// First place where an output differs from the expected one,
+/// found by comparing both as parsed JSON values.
+pub struct Difference {
+    /// Location of the difference, e.g. `[3][1]` or `["key"]`
+    pub path: String,
+    pub output: String,
+    pub expected: String,
+    /// Lengths of the outputs, when they are arrays or strings of different lengths
+    pub lengths: Option<(usize, usize)>,
+    /// Index of the top level element / character the difference is in
+    top_index: Option<usize>,
+    output_value: Value,
+    expected_value: Value,
+}
+
+impl Difference {
+    /// `None` if the outputs are equal or aren't valid JSON
+    pub fn new(output: &str, expected: &str) -> Option<Self> {
+        let output_value: Value = serde_json::from_str(output).ok()?;
+        let expected_value: Value = serde_json::from_str(expected).ok()?;
+        let (path, output, expected) = find(&output_value, &expected_value, String::new())?;
+        let (lengths, top_index) = match (&output_value, &expected_value) {
+            (Value::Array(o), Value::Array(e)) => (
+                (o.len() != e.len()).then_some((o.len(), e.len())),
+                Some(first_mismatch(o.iter(), e.iter())),
+            ),
+            (Value::String(o), Value::String(e)) => {
+                let (o_len, e_len) = (o.chars().count(), e.chars().count());
+                (
+                    (o_len != e_len).then_some((o_len, e_len)),
+                    Some(first_mismatch(o.chars(), e.chars())),
+                )
+            }
+            _ => (None, None),
+        };
+        Some(Self {
+            path,
+            output,
+            expected,
+            lengths,
+            top_index,
+            output_value,
+            expected_value,
+        })
+    }
+}
+
+impl std::fmt::Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Output   : {}",
+            highlight(&self.output_value, self.top_index, output_token)
+        )?;
+        writeln!(
+            f,
+            "Expected : {}",
+            highlight(&self.expected_value, self.top_index, expected_token)
+        )?;
+        let location = if self.path.is_empty() {
+            String::new()
+        } else {
+            format!("at {}, ", self.path.yellow())
+        };
+        write!(
+            f,
+            "Differs  : {}output {}, expected {}",
+            location,
+            self.output.red(),
+            self.expected.green()
+        )?;
+        if let Some((output_len, expected_len)) = self.lengths {
+            write!(
+                f,
+                "\nLength   : output {}, expected {}",
+                output_len.to_string().red(),
+                expected_len.to_string().green()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Colored comparison of two outputs, falling back to
+/// printing both when they can't be compared structurally
+pub fn describe(output: &str, expected: &str) -> String {
+    match Difference::new(output, expected) {
+        Some(difference) => difference.to_string(),
+        None => format!("Output   : {:?}\nExpected : {:?}", output, expected),
+    }
+}
+
+fn first_mismatch<T: PartialEq>(
+    mut output: impl Iterator<Item = T>,
+    mut expected: impl Iterator<Item = T>,
+) -> usize {
+    let mut idx = 0;
+    loop {
+        match (output.next(), expected.next()) {
+            (Some(o), Some(e)) if o == e => idx += 1,
+            _ => return idx,
+        }
+    }
+}
+
+/// Path to the first difference, along with the differing values
+fn find(output: &Value, expected: &Value, path: String) -> Option<(String, String, String)> {
+    if output == expected {
+        return None;
+    }
+    let describe = |value: Option<&Value>| value.map_or("nothing".to_string(), Value::to_string);
+    match (output, expected) {
+        (Value::Array(o), Value::Array(e)) => {
+            let idx = first_mismatch(o.iter(), e.iter());
+            match (o.get(idx), e.get(idx)) {
+                (Some(o), Some(e)) => find(o, e, format!("{path}[{idx}]")),
+                (o, e) => Some((format!("{path}[{idx}]"), describe(o), describe(e))),
+            }
+        }
+        (Value::Object(o), Value::Object(e)) => {
+            let mut keys: Vec<&String> = o.keys().chain(e.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter().find_map(|key| {
+                let path = format!("{path}[{key:?}]");
+                match (o.get(key), e.get(key)) {
+                    (Some(o), Some(e)) => find(o, e, path),
+                    (o, e) => Some((path, describe(o), describe(e))),
+                }
+            })
+        }
+        (Value::String(o), Value::String(e)) => {
+            let idx = first_mismatch(o.chars(), e.chars());
+            let character = |c: Option<char>| c.map_or("nothing".to_string(), |c| format!("{c:?}"));
+            Some((
+                format!("{path} character {idx}").trim_start().to_string(),
+                character(o.chars().nth(idx)),
+                character(e.chars().nth(idx)),
+            ))
+        }
+        _ => Some((path, output.to_string(), expected.to_string())),
+    }
+}
+
+fn output_token(token: &str) -> String {
+    token.red().bold().to_string()
+}
+
+fn expected_token(token: &str) -> String {
+    token.green().bold().to_string()
+}
+
+/// Renders the value around `top_index`, coloring the
+/// element (or the rest of the string) that differs
+fn highlight(value: &Value, top_index: Option<usize>, color: fn(&str) -> String) -> String {
+    let Some(idx) = top_index else {
+        return color(&value.to_string());
+    };
+    match value {
+        Value::Array(items) => {
+            let start = idx.saturating_sub(WINDOW);
+            let end = (idx + WINDOW + 1).min(items.len());
+            let mut tokens: Vec<String> = vec![];
+            if start > 0 {
+                tokens.push("...".to_string());
+            }
+            for (i, item) in items.iter().enumerate().take(end).skip(start) {
+                if i == idx {
+                    tokens.push(color(&item.to_string()));
+                } else {
+                    tokens.push(item.to_string());
+                }
+            }
+            if idx >= items.len() {
+                tokens.push(color("_"));
+            }
+            if end < items.len() {
+                tokens.push("...".to_string());
+            }
+            format!("[{}]", tokens.join(","))
+        }
+        Value::String(string) => {
+            let prefix: String = string.chars().take(idx).collect();
+            let rest: String = string.chars().skip(idx).collect();
+            format!("\"{}{}\"", prefix, color(&rest))
+        }
+        value => color(&value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn differ(output: &str, expected: &str) -> Difference {
+        Difference::new(output, expected).expect("outputs differ")
+    }
+
+    #[test]
+    fn nested_arrays() {
+        let difference = differ("[[1,2],[3,4],[5,6]]", "[[1,2],[3,5],[5,6]]");
+        assert_eq!(difference.path, "[1][1]");
+        assert_eq!(difference.output, "4");
+        assert_eq!(difference.expected, "5");
+        assert_eq!(difference.lengths, None);
+        assert_eq!(difference.top_index, Some(1));
+    }
+
+    #[test]
+    fn length_mismatch() {
+        let difference = differ("[1,2]", "[1,2,3]");
+        assert_eq!(difference.path, "[2]");
+        assert_eq!(difference.output, "nothing");
+        assert_eq!(difference.expected, "3");
+        assert_eq!(difference.lengths, Some((2, 3)));
+
+        let difference = differ("\"abc\"", "\"abd\"");
+        assert_eq!(difference.path, "character 2");
+        assert_eq!(difference.output, "'c'");
+        assert_eq!(difference.expected, "'d'");
+        assert_eq!(difference.lengths, None);
+    }
+
+    #[test]
+    fn objects_and_scalars() {
+        let difference = differ("{\"a\":1,\"b\":2}", "{\"a\":1,\"c\":2}");
+        assert_eq!(difference.path, "[\"b\"]");
+        assert_eq!(difference.output, "2");
+        assert_eq!(difference.expected, "nothing");
+
+        let difference = differ("true", "false");
+        assert_eq!(difference.path, "");
+        assert_eq!(difference.output, "true");
+        assert_eq!(difference.expected, "false");
+    }
+
+    #[test]
+    fn not_json() {
+        assert!(Difference::new("[1,2", "[1,2]").is_none());
+        assert!(Difference::new("abc", "abd").is_none());
+        assert_eq!(
+            describe("abc", "abd"),
+            "Output   : \"abc\"\nExpected : \"abd\""
+        );
+    }
+
+    #[test]
+    fn equal_values() {
+        assert!(Difference::new("[1,2,3]", "[1, 2, 3]").is_none());
+        assert!(Difference::new("{\"a\":[1]}", "{ \"a\": [1] }").is_none());
+    }
+}
@@ -0,0 +1,190 @@
+use colored::Colorize;
+use serde_json::Value;
+
+/// Most rows a chart is drawn with, neighbouring buckets are merged beyond it
+const MAX_ROWS: usize = 16;
+/// Width of the longest bar
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Unit {
+    Millis,
+    Kilobytes,
+}
+
+/// # Result distribution
+/// Share of the accepted submissions of a language per
+/// runtime (ms) or memory (KB) bucket, as shown by leetcode
+#[derive(Debug)]
+pub struct Distribution {
+    /// Start of every bucket along with the percentage of submissions in it
+    pub buckets: Vec<(f64, f64)>,
+    pub unit: Unit,
+}
+
+/// Runtime and memory distributions of a submission,
+/// `None` when leetcode has none for it
+#[derive(Debug)]
+pub struct Distributions {
+    pub runtime: Option<Distribution>,
+    pub memory: Option<Distribution>,
+}
+
+impl Distribution {
+    /// Parses leetcode's `{"lang": .., "distribution": [["12", 3.5], ..]}`
+    pub fn parse(json: &str, unit: Unit) -> Option<Self> {
+        let value: Value = serde_json::from_str(json).ok()?;
+        let number = |value: &Value| match value {
+            Value::String(string) => string.parse::<f64>().ok(),
+            value => value.as_f64(),
+        };
+        let mut buckets = value
+            .get("distribution")?
+            .as_array()?
+            .iter()
+            .map(|bucket| Some((number(bucket.get(0)?)?, number(bucket.get(1)?)?)))
+            .collect::<Option<Vec<_>>>()?;
+        if buckets.is_empty() {
+            return None;
+        }
+        buckets.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(Self { buckets, unit })
+    }
+
+    /// Bar chart of the distribution, marking the row `position` falls in
+    pub fn chart(&self, position: Option<f64>) -> String {
+        let rows: Vec<(f64, f64)> = self
+            .buckets
+            .chunks(self.buckets.len().div_ceil(MAX_ROWS))
+            .map(|chunk| (chunk[0].0, chunk.iter().map(|bucket| bucket.1).sum()))
+            .collect();
+        let highest = rows.iter().map(|row| row.1).fold(0.0, f64::max);
+        let marked = position.map(|position| {
+            rows.iter()
+                .rposition(|row| row.0 <= position)
+                .unwrap_or_default()
+        });
+        let labels: Vec<String> = rows.iter().map(|row| self.format(row.0)).collect();
+        let label_width = labels.iter().map(String::len).max().unwrap_or_default();
+
+        let mut chart = vec![];
+        for (idx, ((_, percentage), label)) in rows.iter().zip(labels).enumerate() {
+            let width = if highest > 0.0 {
+                (percentage / highest * BAR_WIDTH as f64).round() as usize
+            } else {
+                0
+            };
+            let bar = "█".repeat(width.max(usize::from(*percentage > 0.0)));
+            let line = format!("{:>label_width$} │ {} {:.2}%", label, bar, percentage);
+            if Some(idx) == marked {
+                chart.push(format!("{}  {}", line.cyan().bold(), "◀ you".cyan().bold()));
+            } else {
+                chart.push(line);
+            }
+        }
+        chart.join("\n")
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self.unit {
+            Unit::Millis => format!("{} ms", value),
+            Unit::Kilobytes => format!("{:.1} MB", value / 1000.0),
+        }
+    }
+}
+
+impl Distributions {
+    /// Charts of both distributions, marking the runtime and
+    /// memory shown by leetcode, e.g. `3 ms` and `2.1 MB`
+    pub fn display(&self, runtime: &str, memory: &str) {
+        if let Some(distribution) = &self.runtime {
+            println!(
+                "\nRuntime distribution :\n{}",
+                distribution.chart(parse_quantity(runtime))
+            );
+        }
+        if let Some(distribution) = &self.memory {
+            println!(
+                "\nMemory distribution :\n{}",
+                distribution.chart(parse_quantity(memory))
+            );
+        }
+    }
+}
+
+/// Value of a runtime in ms or of a memory in KB, as per the
+/// unit it is shown with
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let (value, unit) = quantity.trim().split_once(' ')?;
+    let value: f64 = value.parse().ok()?;
+    match unit.trim() {
+        "ms" | "KB" => Some(value),
+        "s" | "MB" => Some(value * 1000.0),
+        "GB" => Some(value * 1_000_000.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marked_row(chart: &str) -> Option<usize> {
+        chart.lines().position(|line| line.contains("◀ you"))
+    }
+
+    #[test]
+    fn parses_and_sorts_buckets() {
+        let json = r#"{"lang": "rust", "distribution": [["4", 20.5], ["0", 60], [2, 19.5]]}"#;
+        let distribution = Distribution::parse(json, Unit::Millis).unwrap();
+        assert_eq!(
+            distribution.buckets,
+            [(0.0, 60.0), (2.0, 19.5), (4.0, 20.5)]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_distributions() {
+        let parse = |json| Distribution::parse(json, Unit::Kilobytes);
+        assert!(parse(r#"{"lang": "rust", "distribution": []}"#).is_none());
+        assert!(parse(r#"{"lang": "rust", "distribution": [["a", 1.0]]}"#).is_none());
+        assert!(parse(r#"{"lang": "rust", "distribution": [["1"]]}"#).is_none());
+        assert!(parse(r#"{"lang": "rust"}"#).is_none());
+        assert!(parse("not json").is_none());
+    }
+
+    #[test]
+    fn converts_quantities() {
+        assert_eq!(parse_quantity("3 ms"), Some(3.0));
+        assert_eq!(parse_quantity("17.2 MB"), Some(17200.0));
+        assert_eq!(parse_quantity(" 512 KB "), Some(512.0));
+        assert_eq!(parse_quantity("1 s"), Some(1000.0));
+        assert_eq!(parse_quantity("3ms"), None);
+        assert_eq!(parse_quantity("3 parsecs"), None);
+        assert_eq!(parse_quantity("N/A"), None);
+    }
+
+    #[test]
+    fn marks_the_bucket_of_the_value() {
+        let distribution = Distribution {
+            buckets: vec![(10.0, 50.0), (20.0, 30.0), (30.0, 20.0)],
+            unit: Unit::Millis,
+        };
+        assert_eq!(marked_row(&distribution.chart(Some(25.0))), Some(1));
+        assert_eq!(marked_row(&distribution.chart(Some(99.0))), Some(2));
+        // faster than every bucket
+        assert_eq!(marked_row(&distribution.chart(Some(3.0))), Some(0));
+        assert_eq!(marked_row(&distribution.chart(None)), None);
+    }
+
+    #[test]
+    fn merges_buckets_beyond_the_row_limit() {
+        let distribution = Distribution {
+            buckets: (0..40).map(|start| (start as f64, 2.5)).collect(),
+            unit: Unit::Kilobytes,
+        };
+        let chart = distribution.chart(Some(39.0));
+        assert_eq!(chart.lines().count(), 14);
+        assert_eq!(marked_row(&chart), Some(13));
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::sync::Arc;
+
+/// # Progress event
+/// What the client is doing while it waits on leetcode. The client
+/// doesn't report anything by default, see [`LeetCode::on_event`].
+/// Displayed as the messages the cli prints.
+///
+/// [`LeetCode::on_event`]: super::leetcode::LeetCode::on_event
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Event {
+    /// Testcases are split into batches of what leetcode accepts per run
+    Batches {
+        testcases: usize,
+        batches: usize,
+    },
+    RateLimited {
+        retry_in_secs: u64,
+    },
+    /// The metaData of the question couldn't be parsed,
+    /// the testcases are sent without being validated
+    Unvalidated,
+    /// Testcases were sent and are being run
+    Executing,
+    /// Submission was sent and is being judged
+    Evaluating,
+    Pending,
+    Started,
+    /// Pending state the client doesn't know of
+    UnknownState {
+        state: String,
+    },
+}
+
+/// Called with every event, from the thread making the request
+pub type EventHandler = Arc<dyn Fn(&Event) + Send + Sync>;
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Batches { testcases, batches } => {
+                write!(f, "Running {} testcases in {} batches", testcases, batches)
+            }
+            Event::RateLimited { retry_in_secs } => write!(
+                f,
+                "Rate limited by leetcode, retrying in {} seconds",
+                retry_in_secs
+            ),
+            Event::Unvalidated => write!(
+                f,
+                "Failed to parse question metaData, sending the testcases without validation"
+            ),
+            Event::Executing => write!(f, "Executing testcases..."),
+            Event::Evaluating => write!(f, "Evaluating solution..."),
+            Event::Pending => write!(f, "Status : Pending"),
+            Event::Started => write!(f, "Status : Execution Started"),
+            Event::UnknownState { state } => write!(
+                f,
+                "Status : {}\nKindly report this state to developer",
+                state
+            ),
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use super::diff;
+use crate::file_parser::testcases::NamedInput;
 use colored::Colorize;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Success {
     pub status_code: u8,
     pub lang: String,
@@ -25,30 +27,51 @@ pub struct Success {
     pub expected_task_finish_time: u64,
     pub correct_answer: bool,
     pub compare_result: String,
-    pub total_correct: u8,
-    pub total_testcases: u8,
+    pub total_correct: u16,
+    pub total_testcases: u16,
     pub status_memory: String,
     pub pretty_lang: String,
     pub submission_id: String,
     pub status_msg: String,
     pub state: String,
+    /// Result of every testcase, paired up after execution
+    #[serde(skip_deserializing)]
+    pub cases: Vec<CaseResult>,
 }
 
-#[derive(Debug, Deserialize)]
+/// # Testcase result
+/// Input, output, expected output and stdout of a single testcase.
+/// Entries leetcode didn't send (e.g. the code stopped partway)
+/// are `None` rather than shifting the other testcases.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CaseResult {
+    pub input: NamedInput,
+    pub output: Option<String>,
+    pub expected: Option<String>,
+    pub std_output: String,
+}
+
+impl CaseResult {
+    pub fn is_correct(&self) -> bool {
+        self.output.is_some() && self.output == self.expected
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CompileError {
     pub compile_error: String,
     pub full_compile_error: String,
     pub std_output: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct RuntimeError {
     pub runtime_error: String,
     pub full_runtime_error: String,
     pub std_output: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct LimitExceeded {
     pub status_code: u8,
     pub lang: String,
@@ -73,11 +96,77 @@ impl Success {
     pub fn is_correct(&self) -> bool {
         self.correct_answer
     }
-    pub fn display(&self) {
+
+    /// Indices of the testcases whose output differs from the expected one
+    pub fn failed_cases(&self) -> Vec<usize> {
+        (0..self.cases.len())
+            .filter(|&i| !self.cases[i].is_correct())
+            .collect()
+    }
+
+    /// Pairs up the outputs, expected outputs and stdout by testcase.
+    /// `inputs` are the testcases this result is of, in order.
+    pub fn pair_cases(&mut self, inputs: Vec<NamedInput>) {
+        let len = inputs
+            .len()
+            .max(self.code_answer.len())
+            .max(self.expected_code_answer.len());
+        let mut inputs = inputs.into_iter();
+        // leetcode may send an extra trailing stdout entry,
+        // entries past the last testcase are dropped
+        self.cases = (0..len)
+            .map(|i| CaseResult {
+                input: inputs.next().unwrap_or_default(),
+                output: self.code_answer.get(i).cloned(),
+                expected: self.expected_code_answer.get(i).cloned(),
+                std_output: self.std_output.get(i).cloned().unwrap_or_default(),
+            })
+            .collect();
+    }
+
+    /// Combines the result of another batch of testcases into this one.
+    /// Runtime and memory are those of the slowest / heaviest batch.
+    pub fn merge(&mut self, mut other: Success) {
+        self.cases.append(&mut other.cases);
+        self.run_success &= other.run_success;
+        self.expected_run_success &= other.expected_run_success;
+        self.correct_answer &= other.correct_answer;
+        self.total_correct += other.total_correct;
+        self.total_testcases += other.total_testcases;
+        self.compare_result.push_str(&other.compare_result);
+        self.code_answer.append(&mut other.code_answer);
+        self.code_output.append(&mut other.code_output);
+        self.std_output.append(&mut other.std_output);
+        self.expected_code_answer
+            .append(&mut other.expected_code_answer);
+        self.expected_code_output
+            .append(&mut other.expected_code_output);
+        self.expected_std_output
+            .append(&mut other.expected_std_output);
+        if other.elapsed_time > self.elapsed_time {
+            self.elapsed_time = other.elapsed_time;
+            self.status_runtime = other.status_runtime;
+        }
+        if other.memory > self.memory {
+            self.memory = other.memory;
+            self.status_memory = other.status_memory;
+        }
+        if other.expected_elapsed_time > self.expected_elapsed_time {
+            self.expected_elapsed_time = other.expected_elapsed_time;
+            self.expected_status_runtime = other.expected_status_runtime;
+        }
+        self.expected_memory = self.expected_memory.max(other.expected_memory);
+        if !other.run_success || !other.correct_answer {
+            self.status_msg = other.status_msg;
+        }
+    }
+    /// Displays the result of every testcase, the input of a testcase
+    /// is shown when it fails or when `verbose` is set
+    pub fn display(&self, verbose: bool) {
         let seperator = "-------------------------------";
 
         println!(
-            "\n{}\n\nOutput   : {:?}\nExpected : {:?}\n",
+            "\n{}\n",
             if self.correct_answer {
                 "Testcase execution success".green().bold()
             } else {
@@ -87,15 +176,18 @@ impl Success {
                 )
                 .red()
                 .bold()
-            },
-            self.code_answer,
-            self.expected_code_answer
+            }
         );
 
-        for i in 0..self.code_answer.len() {
-            let is_correct = self.code_answer[i] == self.expected_code_answer[i];
+        let quoted = |value: &Option<String>, missing: &str| {
+            value
+                .as_ref()
+                .map_or(missing.red().to_string(), |value| format!("{value:?}"))
+        };
+        for (i, case) in self.cases.iter().enumerate() {
+            let is_correct = case.is_correct();
             println!(
-                "{}\n{}\n{}\nOutput   : {:?}\nExpected : {:?}\n{}",
+                "{}\n{}\n{}\n{}{}\n{}",
                 seperator.yellow(),
                 if is_correct {
                     format!("Testcase {} execution success", i + 1).green()
@@ -103,12 +195,31 @@ impl Success {
                     format!("Testcase {} execution failed", i + 1).red()
                 },
                 seperator.yellow(),
-                self.code_answer[i],
-                self.expected_code_answer[i],
-                if !self.std_output[i].is_empty() {
-                    format!("\nStd Output :\n{}\n", self.std_output[i])
+                if !case.input.is_empty() && (verbose || !is_correct) {
+                    let params: Vec<String> = case
+                        .input
+                        .iter()
+                        .map(|(name, value)| format!("  {} = {}", name.cyan(), value))
+                        .collect();
+                    format!("Input    :\n{}\n", params.join("\n"))
                 } else {
-                    format!("")
+                    String::new()
+                },
+                match (&case.output, &case.expected) {
+                    (Some(output), Some(expected)) if is_correct => {
+                        format!("Output   : {:?}\nExpected : {:?}", output, expected)
+                    }
+                    (Some(output), Some(expected)) => diff::describe(output, expected),
+                    (output, expected) => format!(
+                        "Output   : {}\nExpected : {}",
+                        quoted(output, "no output"),
+                        quoted(expected, "no expected output")
+                    ),
+                },
+                if !case.std_output.is_empty() {
+                    format!("\nStd Output :\n{}\n", case.std_output)
+                } else {
+                    String::new()
                 }
             );
         }
@@ -129,3 +240,115 @@ impl Success {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn success(code_answer: &[&str], expected: &[&str], std_output: &[&str]) -> Success {
+        let correct = code_answer == expected;
+        serde_json::from_value(serde_json::json!({
+            "status_code": 10,
+            "lang": "rust",
+            "run_success": true,
+            "status_runtime": "0 ms",
+            "memory": 2000,
+            "code_answer": code_answer,
+            "code_output": [],
+            "std_output": std_output,
+            "elapsed_time": 10,
+            "task_finish_time": 0,
+            "expected_status_code": 10,
+            "expected_lang": "cpp",
+            "expected_run_success": true,
+            "expected_status_runtime": "0 ms",
+            "expected_memory": 1000,
+            "expected_code_answer": expected,
+            "expected_code_output": [],
+            "expected_std_output": [],
+            "expected_elapsed_time": 5,
+            "expected_task_finish_time": 0,
+            "correct_answer": correct,
+            "compare_result": if correct { "1".repeat(expected.len()) } else { "0".to_string() },
+            "total_correct": if correct { expected.len() } else { 0 },
+            "total_testcases": expected.len(),
+            "status_memory": "2 MB",
+            "pretty_lang": "Rust",
+            "submission_id": "runcode_1",
+            "status_msg": if correct { "Accepted" } else { "Wrong Answer" },
+            "state": "SUCCESS",
+        }))
+        .unwrap()
+    }
+
+    fn input(value: &str) -> NamedInput {
+        vec![("n".to_string(), value.to_string())]
+    }
+
+    #[test]
+    fn pairs_entries_by_testcase() {
+        let mut result = success(&["1", "2"], &["1", "3"], &["a", "b", ""]);
+        result.pair_cases(vec![input("1"), input("2")]);
+        // the trailing stdout entry is dropped
+        assert_eq!(result.cases.len(), 2);
+        assert_eq!(result.cases[1].input, input("2"));
+        assert_eq!(result.cases[1].output.as_deref(), Some("2"));
+        assert_eq!(result.cases[1].expected.as_deref(), Some("3"));
+        assert_eq!(result.cases[1].std_output, "b");
+        assert_eq!(result.failed_cases(), vec![1]);
+    }
+
+    #[test]
+    fn missing_answers_are_none() {
+        // the code stopped after the first testcase
+        let mut result = success(&["1"], &["1", "2", "3"], &[]);
+        result.pair_cases(vec![input("1"), input("2"), input("3")]);
+        assert_eq!(result.cases.len(), 3);
+        assert_eq!(result.cases[2].output, None);
+        assert_eq!(result.cases[2].expected.as_deref(), Some("3"));
+        assert_eq!(result.cases[2].std_output, "");
+        assert_eq!(result.failed_cases(), vec![1, 2]);
+
+        let mut result = success(&["1", "2"], &["1"], &[]);
+        result.pair_cases(vec![input("1"), input("2")]);
+        assert_eq!(result.cases[1].expected, None);
+        assert!(!result.cases[1].is_correct());
+    }
+
+    #[test]
+    fn missing_inputs_are_empty() {
+        let mut result = success(&["1", "2"], &["1", "2"], &[]);
+        result.pair_cases(vec![input("1")]);
+        assert_eq!(result.cases.len(), 2);
+        assert!(result.cases[1].input.is_empty());
+        assert!(result.failed_cases().is_empty());
+    }
+
+    #[test]
+    fn merges_batches() {
+        let mut result = success(&["1", "2"], &["1", "2"], &["a", "b"]);
+        result.pair_cases(vec![input("1"), input("2")]);
+        let mut other = success(&["4"], &["3"], &["c"]);
+        other.pair_cases(vec![input("3")]);
+        other.elapsed_time = 20;
+        other.status_runtime = "4 ms".to_string();
+        other.expected_run_success = false;
+        result.merge(other);
+
+        assert_eq!(result.cases.len(), 3);
+        assert_eq!(result.cases[2].input, input("3"));
+        assert_eq!(result.failed_cases(), vec![2]);
+        assert_eq!(result.code_answer, ["1", "2", "4"]);
+        assert_eq!(result.expected_code_answer, ["1", "2", "3"]);
+        assert_eq!(result.std_output, ["a", "b", "c"]);
+        assert!(!result.correct_answer);
+        assert!(result.run_success);
+        assert!(!result.expected_run_success);
+        assert_eq!(result.total_correct, 2);
+        assert_eq!(result.total_testcases, 3);
+        assert_eq!(result.status_msg, "Wrong Answer");
+        assert_eq!(result.elapsed_time, 20);
+        assert_eq!(result.status_runtime, "4 ms");
+        assert_eq!(result.memory, 2000);
+    }
+}
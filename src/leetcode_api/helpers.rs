@@ -1,3 +1,4 @@
+use crate::file_parser::language::Language;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize)]
@@ -20,6 +21,11 @@ pub(crate) struct InterpretID {
     pub(crate) interpret_id: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubmissionID {
+    pub(crate) submission_id: u32,
+}
+
 #[allow(non_snake_case)]
 #[derive(Serialize)]
 pub(crate) struct Variables {
@@ -32,7 +38,68 @@ pub(crate) struct QuestionIdQuery {
     pub(crate) variables: String,
 }
 
+impl QuestionIdQuery {
+    /// Query of what running and submitting needs of a question,
+    /// answered with a `QuestionData<Question>`
+    pub(crate) fn metadata(title_slug: &str) -> Self {
+        Self {
+            query: "\n query consolePanelConfig($titleSlug: String!) {\n question(titleSlug: $titleSlug) {\n questionId\n questionFrontendId\n questionTitle\n titleSlug\n enableDebugger\n enableRunCode\n enableSubmit\n enableTestMode\n exampleTestcaseList\n metaData\n }\n}\n".to_string(),
+            variables: serde_json::to_string(&Variables { titleSlug: title_slug.to_string() }).unwrap(),
+        }
+    }
+}
+
+/// Response of a query of a question
+#[derive(Debug, Deserialize)]
+pub(crate) struct QuestionData<T> {
+    pub(crate) data: QuestionWrapper<T>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct QuestionWrapper<T> {
+    pub(crate) question: T,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LeetcodeQuestion {
     pub content: String,
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TopicTag {
+    pub name: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodeSnippet {
+    pub langSlug: String,
+    pub code: String,
+}
+
+/// # Question details
+/// Statement of a question along with what is shown above it
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct QuestionDetails {
+    pub questionFrontendId: String,
+    pub title: String,
+    pub titleSlug: String,
+    pub difficulty: String,
+    pub topicTags: Vec<TopicTag>,
+    /// Missing for paid only questions without a subscription
+    pub content: Option<String>,
+    /// Starting code of the question in every language
+    pub codeSnippets: Option<Vec<CodeSnippet>>,
+}
+
+impl QuestionDetails {
+    /// Starting code of the question in the language
+    pub fn snippet(&self, language: &Language) -> Option<&str> {
+        self.codeSnippets
+            .iter()
+            .flatten()
+            .find(|snippet| Language::from_name(&snippet.langSlug).as_ref() == Some(language))
+            .map(|snippet| snippet.code.as_str())
+    }
+}
@@ -0,0 +1,125 @@
+use colored::Colorize;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SubmissionSummary {
+    pub id: u64,
+    pub lang: String,
+    pub timestamp: u64,
+    pub status_display: String,
+    pub runtime: String,
+    pub memory: String,
+    pub title: String,
+    pub title_slug: String,
+    #[serde(default)]
+    pub code: String,
+}
+
+/// # Submission history
+/// One page of past submissions, latest first
+#[derive(Debug, Deserialize)]
+pub struct SubmissionList {
+    pub submissions_dump: Vec<SubmissionSummary>,
+    pub has_next: bool,
+    /// Key of the next page
+    #[serde(default)]
+    pub last_key: String,
+}
+
+/// # Submission code
+/// Code of a past submission along with its language
+#[derive(Debug)]
+pub struct SubmissionCode {
+    pub id: u64,
+    pub lang: String,
+    pub title_slug: String,
+    pub code: String,
+    pub runtime: String,
+    pub memory: String,
+    /// Only known when fetched through the submission details
+    pub runtime_percentile: Option<f64>,
+    pub memory_percentile: Option<f64>,
+}
+
+impl From<SubmissionSummary> for SubmissionCode {
+    fn from(submission: SubmissionSummary) -> Self {
+        Self {
+            id: submission.id,
+            lang: submission.lang,
+            title_slug: submission.title_slug,
+            code: submission.code,
+            runtime: submission.runtime,
+            memory: submission.memory,
+            runtime_percentile: None,
+            memory_percentile: None,
+        }
+    }
+}
+
+impl SubmissionSummary {
+    pub fn is_accepted(&self) -> bool {
+        self.status_display == "Accepted"
+    }
+}
+
+impl SubmissionList {
+    pub fn display(&self) {
+        println!("{}", self);
+    }
+}
+
+impl std::fmt::Display for SubmissionList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.submissions_dump.is_empty() {
+            return write!(f, "No submissions found");
+        }
+        writeln!(
+            f,
+            "{:<12}{:<18}{:<24}{:<12}{:<10}{:<10}Question",
+            "Id", "Time (UTC)", "Status", "Language", "Runtime", "Memory"
+        )?;
+        for submission in &self.submissions_dump {
+            let status = format!("{:<24}", submission.status_display);
+            writeln!(
+                f,
+                "{:<12}{:<18}{}{:<12}{:<10}{:<10}{}",
+                submission.id,
+                format_timestamp(submission.timestamp),
+                if submission.is_accepted() {
+                    status.green()
+                } else {
+                    status.red()
+                },
+                submission.lang,
+                submission.runtime,
+                submission.memory,
+                submission.title_slug
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `YYYY-MM-DD HH:MM` of a unix timestamp, in UTC
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+    // civil date from days since epoch, Howard Hinnant's algorithm
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
@@ -1,15 +1,96 @@
+use super::distribution::{Distribution, Distributions, Unit};
+use super::event::{Event, EventHandler};
 use super::helpers::*;
+use super::history::{SubmissionCode, SubmissionList, SubmissionSummary};
+use super::metadata::MetaData;
+use super::nonblocking::AsyncLeetCode;
+use super::problems::ProblemList;
+use super::protocol::*;
 use super::user::*;
 use super::worker::*;
 use crate::file_parser::codefile::CodeFile;
+use crate::file_parser::testcases::Testcases;
 use serde::Deserialize;
+use std::time::Duration;
 
+/// Delay between two checks of a pending result
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Times a rate limited run is retried before giving up
+const RATE_LIMIT_RETRIES: u32 = 3;
+/// Wait before the first retry of a rate limited run, grows linearly
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(5);
+
+/// # LeetCode region
+/// Site the requests are sent to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Region {
+    #[default]
+    Global,
+    China,
+}
+
+impl Region {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Region::Global => "https://leetcode.com",
+            Region::China => "https://leetcode.cn",
+        }
+    }
+
+    /// Link to the question on the site of the region
+    pub fn problem_url(&self, title_slug: &str) -> String {
+        format!("{}/problems/{}/", self.base_url(), title_slug)
+    }
+}
+
+/// # Poll timing
+/// How often pending results are checked and how rate limited runs are retried
+#[derive(Debug, Clone, Copy)]
+pub struct Timing {
+    pub poll_interval: Duration,
+    pub rate_limit_retries: u32,
+    /// Wait before the first retry, grows linearly
+    pub rate_limit_backoff: Duration,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            poll_interval: POLL_INTERVAL,
+            rate_limit_retries: RATE_LIMIT_RETRIES,
+            rate_limit_backoff: RATE_LIMIT_BACKOFF,
+        }
+    }
+}
+
+/// State of a client holding the session of a user
 pub struct Authorized;
+/// State of a new client, only configurable until authenticated
 pub struct Unauthorized;
 
+/// # LeetCode client
+/// Blocking client of leetcode's API. A new client is configured with
+/// its builder methods and then authenticated with the cookie of a
+/// session, which gives the client every request.
+///
+/// ```no_run
+/// use leetcode_runner_cli::file_parser::codefile::CodeFile;
+/// use leetcode_runner_cli::leetcode_api::leetcode::LeetCode;
+///
+/// let lc = LeetCode::new()
+///     .on_event(|event| eprintln!("{}", event))
+///     .authenticate("LEETCODE_SESSION=...; csrftoken=...")?;
+/// let code = CodeFile::try_from_file("two-sum.rs".as_ref())?;
+/// let result = lc.execute_default(&code)?;
+/// # Ok::<(), String>(())
+/// ```
 pub struct LeetCode<State = Unauthorized> {
     state: std::marker::PhantomData<State>,
     client: reqwest::blocking::Client,
+    region: Region,
+    timing: Timing,
+    events: EventHandler,
 }
 
 impl LeetCode {
@@ -17,53 +98,112 @@ impl LeetCode {
         LeetCode {
             state: std::marker::PhantomData::<Unauthorized>,
             client: Default::default(),
+            region: Default::default(),
+            timing: Default::default(),
+            events: std::sync::Arc::new(|_| {}),
         }
     }
 }
 
+impl Default for LeetCode<Unauthorized> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<State> LeetCode<State> {
+    /// Full url of a path on the site of the region
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.region.base_url(), path)
+    }
+
+    fn emit(&self, event: Event) {
+        (self.events)(&event);
+    }
+}
+
 impl LeetCode<Unauthorized> {
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = region;
+        self
+    }
+
+    pub fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Handles the progress events, which are dropped otherwise
+    pub fn on_event(mut self, handler: impl Fn(&Event) + Send + Sync + 'static) -> Self {
+        self.events = std::sync::Arc::new(handler);
+        self
+    }
+
     /// # Authenticate with cookie
     /// Builds a new reqwest client with the cookie
     pub fn authenticate(&mut self, cookie: &str) -> Result<LeetCode<Authorized>, String> {
-        let mut headers = reqwest::header::HeaderMap::with_capacity(5);
-        let Some(csrf_token) = cookie
-            .split(';')
-            .find(|s| s.contains("csrftoken"))
-            else{ Err("No csrf token found".to_string())? };
-        let Some(csrf_token) = csrf_token.split('=').last() else{ Err("No csrf token found".to_string())? };
-        let csrf_token = csrf_token.to_string();
-        headers.insert(
-            reqwest::header::COOKIE,
-            reqwest::header::HeaderValue::from_str(&cookie).unwrap(),
-        );
-        headers.insert(
-            reqwest::header::USER_AGENT,
-            reqwest::header::HeaderValue::from_str("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/110.0.0.0 Safari/537.36").unwrap(),
-        );
-        headers.insert(
-            reqwest::header::REFERER,
-            reqwest::header::HeaderValue::from_str("https://leetcode.com/").unwrap(),
-        );
-        headers.insert(
-            reqwest::header::HeaderName::from_static("x-csrftoken"),
-            reqwest::header::HeaderValue::from_str(csrf_token.as_str()).unwrap(),
-        );
         let client = reqwest::blocking::Client::builder()
-            .default_headers(headers.clone())
+            .default_headers(session_headers(cookie, self.region)?)
             .build()
             .unwrap();
         Ok(LeetCode {
             state: std::marker::PhantomData::<Authorized>,
             client,
+            region: self.region,
+            timing: self.timing,
+            events: self.events.clone(),
         })
     }
+
+    /// Like `authenticate`, for an async client along with the same settings
+    pub fn authenticate_async(&self, cookie: &str) -> Result<AsyncLeetCode, String> {
+        let client = reqwest::Client::builder()
+            .default_headers(session_headers(cookie, self.region)?)
+            .build()
+            .unwrap();
+        Ok(AsyncLeetCode {
+            client,
+            region: self.region,
+            timing: self.timing,
+            events: self.events.clone(),
+        })
+    }
+}
+
+/// Headers of every request of the session the cookie is of
+fn session_headers(cookie: &str, region: Region) -> Result<reqwest::header::HeaderMap, String> {
+    let mut headers = reqwest::header::HeaderMap::with_capacity(5);
+    let Some(csrf_token) = cookie.split(';').find(|s| s.contains("csrftoken")) else {
+        Err("No csrf token found".to_string())?
+    };
+    let Some(csrf_token) = csrf_token.split('=').next_back() else {
+        Err("No csrf token found".to_string())?
+    };
+    let csrf_token = csrf_token.to_string();
+    headers.insert(
+        reqwest::header::COOKIE,
+        reqwest::header::HeaderValue::from_str(cookie).unwrap(),
+    );
+    headers.insert(
+        reqwest::header::USER_AGENT,
+        reqwest::header::HeaderValue::from_str("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/110.0.0.0 Safari/537.36").unwrap(),
+    );
+    headers.insert(
+        reqwest::header::REFERER,
+        reqwest::header::HeaderValue::from_str(&format!("{}/", region.base_url())).unwrap(),
+    );
+    headers.insert(
+        reqwest::header::HeaderName::from_static("x-csrftoken"),
+        reqwest::header::HeaderValue::from_str(csrf_token.as_str()).unwrap(),
+    );
+    Ok(headers)
 }
 
 impl LeetCode<Authorized> {
     pub fn get_metadata(&self) -> Result<UserMetadata, String> {
         let client = &self.client;
         let Ok(data) = client
-            .get("https://leetcode.com/api/problems/all/")
+            .get(self.url("/api/problems/all/"))
             .send() else {
                 return Err("Failed to fetch metadata from leetcode".to_string());
             };
@@ -72,21 +212,224 @@ impl LeetCode<Authorized> {
             .json::<UserMetadata>()
             .map_err(|_| "Failed to parse metadata, try renewing cookie".to_string());
         if let Ok(metadata) = metadata.as_ref() {
-            if metadata.user_name == "" {
+            if metadata.user_name.is_empty() {
                 return Err(String::from("Cookie invalid. Renew cookies"));
             }
         }
         metadata
     }
 
+    /// Every question of the site, along with whether it is solved
+    pub fn problems(&self) -> Result<ProblemList, String> {
+        let client = &self.client;
+        let Ok(data) = client
+            .get(self.url("/api/problems/all/"))
+            .send() else {
+                return Err("Failed to fetch problems from leetcode".to_string());
+            };
+        data.json::<ProblemList>()
+            .map_err(|_| "Failed to parse the problem list".to_string())
+    }
+
+    /// # Submission history
+    /// One page of past submissions of the question, or of every question
+    /// when no title is given. `offset` and `limit` select the page, which
+    /// leetcode only sends along with the `last_key` of the page before it
+    /// (empty for the first page).
+    pub fn submissions(
+        &self,
+        title_slug: Option<&str>,
+        offset: usize,
+        limit: usize,
+        last_key: &str,
+    ) -> Result<SubmissionList, String> {
+        let client = &self.client;
+        let url = match title_slug {
+            Some(title_slug) => self.url(&format!("/api/submissions/{}/", title_slug)),
+            None => self.url("/api/submissions/"),
+        };
+        let Ok(data) = client
+            .get(url)
+            .query(&[("offset", offset), ("limit", limit)])
+            .query(&[("lastkey", last_key)])
+            .send() else {
+                return Err("Failed to fetch submissions from leetcode".to_string());
+            };
+        data.json::<SubmissionList>()
+            .map_err(|_| "Failed to parse submissions, try renewing cookie".to_string())
+    }
+
+    /// Code of the submission with the given id
+    pub fn submission_code(&self, submission_id: u64) -> Result<SubmissionCode, String> {
+        let client = &self.client;
+        let url = self.url("/graphql");
+        let query = serde_json::json!({
+            "query": "query submissionDetails($submissionId: Int!) { submissionDetails(submissionId: $submissionId) { code runtimeDisplay runtimePercentile memoryDisplay memoryPercentile lang { name } question { titleSlug } } }",
+            "variables": { "submissionId": submission_id },
+        });
+        let Ok(data) = client.post(&url).json(&query).send() else {
+                return Err("Failed to fetch submission from leetcode".to_string());
+            };
+
+        #[derive(Deserialize)]
+        struct Lang {
+            name: String,
+        }
+        #[allow(non_snake_case)]
+        #[derive(Deserialize)]
+        struct QuestionSlug {
+            titleSlug: String,
+        }
+        #[allow(non_snake_case)]
+        #[derive(Deserialize)]
+        struct Details {
+            code: String,
+            runtimeDisplay: String,
+            runtimePercentile: Option<f64>,
+            memoryDisplay: String,
+            memoryPercentile: Option<f64>,
+            lang: Lang,
+            question: QuestionSlug,
+        }
+        #[allow(non_snake_case)]
+        #[derive(Deserialize)]
+        struct DetailsWrapper {
+            submissionDetails: Option<Details>,
+        }
+        #[derive(Deserialize)]
+        struct Data {
+            data: DetailsWrapper,
+        }
+
+        let Ok(data) = data.json::<Data>() else {
+            return Err("Failed to parse submission, try renewing cookie".to_string());
+        };
+        let Some(details) = data.data.submissionDetails else {
+            return Err(format!("Submission {} not found", submission_id));
+        };
+        Ok(SubmissionCode {
+            id: submission_id,
+            lang: details.lang.name,
+            title_slug: details.question.titleSlug,
+            code: details.code,
+            runtime: details.runtimeDisplay,
+            memory: details.memoryDisplay,
+            runtime_percentile: details.runtimePercentile,
+            memory_percentile: details.memoryPercentile,
+        })
+    }
+
+    /// Runtime and memory distributions of the accepted submissions
+    /// in the language of the given submission
+    pub fn submission_distributions(&self, submission_id: &str) -> Result<Distributions, String> {
+        let Ok(submission_id) = submission_id.parse::<u64>() else {
+            return Err(format!("Invalid submission id {}", submission_id));
+        };
+        let client = &self.client;
+        let url = self.url("/graphql");
+        let query = serde_json::json!({
+            "query": "query submissionDetails($submissionId: Int!) { submissionDetails(submissionId: $submissionId) { runtimeDistribution memoryDistribution } }",
+            "variables": { "submissionId": submission_id },
+        });
+        let Ok(data) = client.post(&url).json(&query).send() else {
+                return Err("Failed to fetch submission from leetcode".to_string());
+            };
+
+        #[allow(non_snake_case)]
+        #[derive(Deserialize)]
+        struct Details {
+            runtimeDistribution: Option<String>,
+            memoryDistribution: Option<String>,
+        }
+        #[allow(non_snake_case)]
+        #[derive(Deserialize)]
+        struct DetailsWrapper {
+            submissionDetails: Option<Details>,
+        }
+        #[derive(Deserialize)]
+        struct Data {
+            data: DetailsWrapper,
+        }
+
+        let Ok(data) = data.json::<Data>() else {
+            return Err("Failed to parse submission distributions".to_string());
+        };
+        let Some(details) = data.data.submissionDetails else {
+            return Err(format!("Submission {} not found", submission_id));
+        };
+        Ok(Distributions {
+            runtime: details
+                .runtimeDistribution
+                .and_then(|json| Distribution::parse(&json, Unit::Millis)),
+            memory: details
+                .memoryDistribution
+                .and_then(|json| Distribution::parse(&json, Unit::Kilobytes)),
+        })
+    }
+
+    /// Latest accepted submission of every question, latest first.
+    /// Stops at submissions with an id up to `after`, which are
+    /// taken as already seen.
+    pub fn accepted_submissions(&self, after: u64) -> Result<Vec<SubmissionSummary>, String> {
+        const PAGE: usize = 20;
+        let mut accepted: Vec<SubmissionSummary> = vec![];
+        let mut offset = 0;
+        let mut last_key = String::new();
+        loop {
+            let submissions = self.submissions(None, offset, PAGE, &last_key)?;
+            let has_next = submissions.has_next;
+            last_key = submissions.last_key;
+            for submission in submissions.submissions_dump {
+                if submission.id <= after {
+                    return Ok(accepted);
+                }
+                if submission.is_accepted()
+                    && !accepted
+                        .iter()
+                        .any(|seen| seen.title_slug == submission.title_slug)
+                {
+                    accepted.push(submission);
+                }
+            }
+            if !has_next {
+                return Ok(accepted);
+            }
+            offset += PAGE;
+            std::thread::sleep(self.timing.poll_interval);
+        }
+    }
+
+    /// Code of the latest accepted submission of the question
+    pub fn latest_accepted(&self, title_slug: &str) -> Result<SubmissionCode, String> {
+        const PAGE: usize = 20;
+        let mut offset = 0;
+        let mut last_key = String::new();
+        loop {
+            let submissions = self.submissions(Some(title_slug), offset, PAGE, &last_key)?;
+            let has_next = submissions.has_next;
+            last_key = submissions.last_key;
+            if let Some(accepted) = submissions
+                .submissions_dump
+                .into_iter()
+                .find(|submission| submission.is_accepted())
+            {
+                return Ok(accepted.into());
+            }
+            if !has_next {
+                return Err(format!("No accepted submission found for {}", title_slug));
+            }
+            offset += PAGE;
+        }
+    }
+
     pub fn question_content(&self, title_slug: &str) -> Result<LeetcodeQuestion, String> {
         let client = &self.client;
-        let url = "https://leetcode.com/graphql";
+        let url = self.url("/graphql");
         let query = QuestionIdQuery {
             query:  "query questionContent($titleSlug: String!) { question(titleSlug: $titleSlug) { content    mysqlSchemas }}".to_string(),
             variables: serde_json::to_string(&Variables { titleSlug: title_slug.to_string() }).unwrap(),
         };
-        let Ok(data) = client.post(url).json(&query).send() else {
+        let Ok(data) = client.post(&url).json(&query).send() else {
                 return Err("Failed to fetch question id from leetcode".to_string());
             };
         #[derive(Deserialize)]
@@ -104,205 +447,157 @@ impl LeetCode<Authorized> {
             .map(|op| op.data.question)
     }
 
-    pub fn question_metadata(&self, title_slug: &str) -> Result<Question, String> {
+    pub fn question_details(&self, title_slug: &str) -> Result<QuestionDetails, String> {
         let client = &self.client;
-        let url = "https://leetcode.com/graphql";
-
+        let url = self.url("/graphql");
         let query = QuestionIdQuery {
-            query: "\n query consolePanelConfig($titleSlug: String!) {\n question(titleSlug: $titleSlug) {\n questionId\n questionFrontendId\n questionTitle\n enableDebugger\n enableRunCode\n enableSubmit\n enableTestMode\n exampleTestcaseList\n metaData\n }\n}\n".to_string(),
+            query: "query questionDetails($titleSlug: String!) { question(titleSlug: $titleSlug) { questionFrontendId title titleSlug difficulty topicTags { name } content codeSnippets { langSlug code } } }".to_string(),
             variables: serde_json::to_string(&Variables { titleSlug: title_slug.to_string() }).unwrap(),
         };
-        let Ok(data) = client.post(url).json(&query).send() else {
-                return Err("Failed to fetch question id from leetcode".to_string());
+        let Ok(data) = client.post(&url).json(&query).send() else {
+                return Err("Failed to fetch question details from leetcode".to_string());
             };
-
-        #[derive(Debug, Deserialize)]
+        #[derive(Deserialize)]
         struct QuestionWrapper {
-            question: Question,
+            question: QuestionDetails,
         }
 
-        #[derive(Debug, Deserialize)]
+        #[derive(Deserialize)]
         struct Data {
             data: QuestionWrapper,
         }
 
         data.json::<Data>()
+            .map_err(|_| format!("Failed to parse details of {}", title_slug))
+            .map(|op| op.data.question)
+    }
+
+    pub fn question_metadata(&self, title_slug: &str) -> Result<Question, String> {
+        let client = &self.client;
+        let url = self.url("/graphql");
+
+        let query = QuestionIdQuery::metadata(title_slug);
+        let Ok(data) = client.post(&url).json(&query).send() else {
+                return Err("Failed to fetch question id from leetcode".to_string());
+            };
+
+        data.json::<QuestionData<Question>>()
             .map_err(|_| "Failed to parse question id from leetcode".to_string())
             .map(|opt| opt.data.question)
     }
+
+    /// MetaData the testcases of the question are validated and split with.
+    /// When leetcode's metaData can't be parsed the input is sent as is.
+    pub fn meta_data(&self, question: &Question) -> MetaData {
+        meta_data_or_default(question, &self.events)
+    }
+
     pub fn execute_default(&self, codefile: &CodeFile) -> Result<ExecutionResult, String> {
         self.execute(codefile, String::new())
     }
+    /// # Execute testcases
+    /// Runs `data_input` against the code. When no input is given,
+    /// the examples of the question are merged with every testcase
+    /// file of the per-problem testcase directory (saved regressions included).
+    ///
+    /// Inputs are split into batches small enough for leetcode to accept
+    /// and the results of all the batches are combined into one.
     pub fn execute(
         &self,
         codefile: &CodeFile,
-        mut data_input: String,
+        data_input: String,
     ) -> Result<ExecutionResult, String> {
         let question_title = codefile.question_title.clone();
         let ques = self.question_metadata(&question_title)?;
-        if data_input == "" {
-            data_input = ques.exampleTestcaseList.join("\n");
+        let meta_data = self.meta_data(&ques);
+        let testcases = ques.testcases(&meta_data, &data_input)?;
+        self.execute_testcases(codefile, &ques.questionId, &meta_data, &testcases)
+    }
+
+    /// Runs the testcases in batches and combines the results,
+    /// the first batch failing to compile or run is returned as is
+    pub fn execute_testcases(
+        &self,
+        codefile: &CodeFile,
+        question_id: &str,
+        meta_data: &MetaData,
+        testcases: &Testcases,
+    ) -> Result<ExecutionResult, String> {
+        let mut run = BatchedRun::new(testcases, meta_data, &self.events)?;
+        while let Some(data_input) = run.next_batch() {
+            let result = self.execute_batch(codefile, question_id, data_input)?;
+            if let Some(failed) = run.add(result) {
+                return Ok(failed);
+            }
         }
-        let question_id = ques.questionId;
-        self._execute(
-            codefile.language.to_string(),
-            question_id,
-            question_title,
-            codefile.code.clone(),
-            data_input,
-        )
+        Ok(run.finish())
     }
 
-    fn _execute(
+    fn execute_batch(
         &self,
-        lang: String,
-        question_id: String,
-        question_title: String,
-        typed_code: String,
+        codefile: &CodeFile,
+        question_id: &str,
         data_input: String,
     ) -> Result<ExecutionResult, String> {
-        let client = &self.client;
-        let url = format!(
-            "https://leetcode.com/problems/{}/interpret_solution/",
-            question_title
-        );
-        let testcase = TestCaseExec {
-            lang,
-            question_id,
-            question_title,
-            typed_code,
-            data_input,
-        };
-        let Ok(data)= client.post(&url).json(&testcase).send() else {
-                return Err("Failed to parse arguments".to_string());
-            };
-        let Ok(data) = data.json::<InterpretID>() else{
-                return Err("Failed to parse JSON from leetcode. Try again after sometime or renew cookie".to_string());
-            };
-
-        let interpret_id = data.interpret_id;
-        println!("Executing testcases...");
-        let mut last_state = 0;
-        loop {
-            let url = format!("https://leetcode.com/submissions/detail/{interpret_id}/check/");
-            // std::thread::sleep(std::time::Duration::from_secs(7));
-            let Ok(data) = client.get(&url).send() else {
-                return Err("Failed to parse arguments".to_string());
+        let url = self.url(&interpret_path(codefile));
+        let testcase = TestCaseExec::new(codefile, question_id, data_input);
+        let mut rate_limit = RateLimit::new(self.timing, &self.events);
+        let data = loop {
+            let Ok(data) = self.client.post(&url).json(&testcase).send() else {
+                return Err(REQUEST_FAILED.to_string());
             };
-
-            let Ok(data) = data.json::<ExecutionResult>() else  {
-                    return Err("Failed to parse JSON from leetcode. Try again after sometime or renew cookie".to_string());
-              };
-            match data {
-                ExecutionResult::PendingResult(data) => {
-                    last_state = match data.state.as_str() {
-                        "PENDING" => {
-                            if last_state == 0 {
-                                println!("Status : Pending");
-                            }
-                            1
-                        }
-                        "STARTED" => {
-                            if last_state == 1 {
-                                println!("Status : Execution Started");
-                            }
-                            2
-                        }
-                        _ => {
-                            if last_state == 2 {
-                                println!(
-                                    "Status : {}\nKindly report this state to developer",
-                                    data.state.as_str()
-                                );
-                            }
-                            3
-                        }
-                    };
-                    continue;
-                }
-                data => return Ok(data),
-            };
-        }
+            match rate_limit.retry_after(data.status()) {
+                Some(backoff) => std::thread::sleep(backoff),
+                None => break data,
+            }
+        };
+        let Ok(data) = data.json::<InterpretID>() else {
+            return Err(PARSE_FAILED.to_string());
+        };
+        self.emit(Event::Executing);
+        self.poll(&data.interpret_id)
     }
+
     pub fn submit(&self, codefile: &CodeFile) -> Result<SubmissionResult, String> {
         let question_title = codefile.question_title.clone();
         let ques = self.question_metadata(&question_title)?;
-        let question_id = ques.questionId;
-        self._submit(
-            codefile.language.to_string(),
-            question_id,
-            question_title,
-            codefile.code.clone(),
-        )
+        self.submit_question(codefile, &ques.questionId)
     }
 
-    fn _submit(
+    /// Submits the code to the question of the given id, for
+    /// callers which already fetched the metadata of the question
+    pub fn submit_question(
         &self,
-        lang: String,
-        question_id: String,
-        question_title: String,
-        typed_code: String,
+        codefile: &CodeFile,
+        question_id: &str,
     ) -> Result<SubmissionResult, String> {
-        let client = &self.client;
-        let url = format!("https://leetcode.com/problems/{}/submit/", question_title);
-        let submission = SubmitCode {
-            lang,
-            question_id,
-            typed_code,
+        let url = self.url(&submit_path(codefile));
+        let submission = SubmitCode::new(codefile, question_id);
+        let Ok(data) = self.client.post(&url).json(&submission).send() else {
+            return Err(REQUEST_FAILED.to_string());
         };
-        let Ok(data)= client.post(&url).json(&submission).send() else {
-                return Err("Failed to parse arguments".to_string());
-            };
-        #[derive(Debug, Deserialize)]
-        struct SubmissionID {
-            submission_id: u32,
-        }
-        // println!("{}", data.text().unwrap());
         let Ok(data) = data.json::<SubmissionID>() else {
-                return Err("Failed to fetch submission id from leetcode. Check your submissions manually on leetcode".to_string());
-            };
-        println!("Evaluating solution...");
-        let submission_id = data.submission_id;
-        let mut last_state = 0;
+            return Err(NO_SUBMISSION_ID.to_string());
+        };
+        self.emit(Event::Evaluating);
+        self.poll(&data.submission_id.to_string())
+    }
 
+    /// Checks the result of a run or a submission until it isn't pending
+    fn poll<T: Pollable>(&self, id: &str) -> Result<T, String> {
+        let url = self.url(&check_path(id));
+        let mut poll = Poll::new(&self.events);
         loop {
-            let url = format!("https://leetcode.com/submissions/detail/{submission_id}/check/");
-            let Ok(data) = client.get(&url).send() else {
-                return Err("Failed to parse arguments".to_string());
+            std::thread::sleep(self.timing.poll_interval);
+            let Ok(data) = self.client.get(&url).send() else {
+                return Err(REQUEST_FAILED.to_string());
             };
-
-            let Ok(data) = data.json::<SubmissionResult>() else  {
-                    return Err("Failed to fetch from leetcode. Try again after sometime or renew cookie".to_string());
-              };
-            match data {
-                SubmissionResult::PendingResult(data) => {
-                    last_state = match data.state.as_str() {
-                        "PENDING" => {
-                            if last_state == 0 {
-                                println!("Status : Evalutaion Pending");
-                            }
-                            1
-                        }
-                        "STARTED" => {
-                            if last_state == 1 {
-                                println!("Status : Execution Started");
-                            }
-                            2
-                        }
-                        _ => {
-                            if last_state == 2 {
-                                println!(
-                                    "Status : {}\nKindly report this state to developer",
-                                    data.state.as_str()
-                                );
-                            }
-                            3
-                        }
-                    };
-                    continue;
-                }
-                data => return Ok(data),
+            let Ok(data) = data.json::<T>() else {
+                return Err(PARSE_FAILED.to_string());
             };
+            if let Some(data) = poll.done(data) {
+                return Ok(data);
+            }
         }
     }
 }
@@ -0,0 +1,300 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// # Question metaData
+/// Parsed form of the `metaData` JSON string leetcode attaches to every
+/// question. Describes the function signature the judge calls.
+#[derive(Debug, Deserialize, Default)]
+pub struct MetaData {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<Param>,
+    #[serde(rename = "return")]
+    pub return_type: Option<ReturnType>,
+    #[serde(default)]
+    pub systemdesign: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Param {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReturnType {
+    #[serde(rename = "type")]
+    pub return_type: String,
+}
+
+impl MetaData {
+    pub fn parse(meta_data: &str) -> Result<Self, String> {
+        serde_json::from_str(meta_data).map_err(|_| "Failed to parse question metaData".to_string())
+    }
+
+    /// Names of the input lines of a testcase
+    pub fn param_names(&self) -> Vec<&str> {
+        if self.systemdesign {
+            vec!["methods", "arguments"]
+        } else {
+            self.params
+                .iter()
+                .map(|param| param.name.as_str())
+                .collect()
+        }
+    }
+
+    /// Number of input lines that make up one testcase.
+    ///
+    /// Design questions always take two lines (method names and their
+    /// arguments), regular questions take one line per parameter.
+    /// `None` means the question has no parameter list (e.g. database
+    /// questions) and the input can't be split into cases.
+    pub fn lines_per_case(&self) -> Option<usize> {
+        if self.systemdesign {
+            Some(2)
+        } else if self.params.is_empty() {
+            None
+        } else {
+            Some(self.params.len())
+        }
+    }
+}
+
+/// # Parameter type
+/// Type of a parameter as declared in the metaData,
+/// used to check the shape of the testcase input locally
+#[derive(Debug, PartialEq)]
+pub enum ParamType {
+    Integer,
+    Long,
+    Double,
+    Boolean,
+    Character,
+    String,
+    TreeNode,
+    ListNode,
+    List(Box<ParamType>),
+    /// Types not known to the tool, any value is accepted
+    Unknown(String),
+}
+
+impl ParamType {
+    pub fn parse(param_type: &str) -> Self {
+        let param_type = param_type.trim();
+        if let Some(inner) = param_type.strip_suffix("[]") {
+            return ParamType::List(Box::new(Self::parse(inner)));
+        }
+        if let Some(inner) = param_type
+            .strip_prefix("list<")
+            .and_then(|inner| inner.strip_suffix('>'))
+        {
+            return ParamType::List(Box::new(Self::parse(inner)));
+        }
+        match param_type {
+            "integer" | "int" => ParamType::Integer,
+            "long" => ParamType::Long,
+            "double" | "float" => ParamType::Double,
+            "boolean" => ParamType::Boolean,
+            "character" | "char" => ParamType::Character,
+            "string" => ParamType::String,
+            "TreeNode" => ParamType::TreeNode,
+            "ListNode" => ParamType::ListNode,
+            unknown => ParamType::Unknown(unknown.to_string()),
+        }
+    }
+
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            ParamType::Integer => value.as_i64().is_some_and(|int| i32::try_from(int).is_ok()),
+            ParamType::Long => value.as_i64().is_some(),
+            ParamType::Double => value.is_number(),
+            ParamType::Boolean => value.is_boolean(),
+            ParamType::Character => value.as_str().is_some_and(|c| c.chars().count() == 1),
+            ParamType::String => value.is_string(),
+            ParamType::TreeNode => value.as_array().is_some_and(|nodes| {
+                nodes
+                    .iter()
+                    .all(|node| node.is_null() || ParamType::Integer.matches(node))
+            }),
+            ParamType::ListNode => value
+                .as_array()
+                .is_some_and(|nodes| nodes.iter().all(|node| ParamType::Integer.matches(node))),
+            ParamType::List(inner) => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(|item| inner.matches(item))),
+            ParamType::Unknown(_) => true,
+        }
+    }
+}
+
+impl MetaData {
+    /// # Validate testcase input
+    /// Checks that the input is made of whole testcases and that every line
+    /// parses as the type of the parameter it is given for.
+    /// The error points to the line (1-based, blank lines counted) at fault.
+    pub fn validate(&self, input: &str) -> Result<(), String> {
+        let Some(lines_per_case) = self.lines_per_case() else {
+            return Ok(());
+        };
+        let lines: Vec<(usize, &str)> = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+        for (idx, (line_no, line)) in lines.iter().enumerate() {
+            let (name, param_type) = if self.systemdesign {
+                if idx % 2 == 0 {
+                    ("methods", "string[]")
+                } else {
+                    ("arguments", "any[][]")
+                }
+            } else {
+                let param = &self.params[idx % lines_per_case];
+                (param.name.as_str(), param.param_type.as_str())
+            };
+            let valid = serde_json::from_str::<Value>(line)
+                .is_ok_and(|value| ParamType::parse(param_type).matches(&value));
+            if !valid {
+                return Err(format!(
+                    "Invalid testcase input at line {} : expected {} for parameter `{}`, found `{}`",
+                    line_no, param_type, name, line
+                ));
+            }
+        }
+        if !lines.len().is_multiple_of(lines_per_case) {
+            let (line_no, _) = lines[lines.len() - lines.len() % lines_per_case];
+            let missing: Vec<String> = if self.systemdesign {
+                vec!["`arguments`".to_string()]
+            } else {
+                self.params[lines.len() % lines_per_case..]
+                    .iter()
+                    .map(|param| format!("`{}`", param.name))
+                    .collect()
+            };
+            return Err(format!(
+                "Incomplete testcase starting at line {} : missing {}",
+                line_no,
+                missing.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta_data(params: &[(&str, &str)]) -> MetaData {
+        MetaData {
+            name: "solve".to_string(),
+            params: params
+                .iter()
+                .map(|(name, param_type)| Param {
+                    name: name.to_string(),
+                    param_type: param_type.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_nested_types() {
+        assert_eq!(
+            ParamType::parse("character[][]"),
+            ParamType::List(Box::new(ParamType::List(Box::new(ParamType::Character))))
+        );
+        assert_eq!(
+            ParamType::parse("list<integer>"),
+            ParamType::List(Box::new(ParamType::Integer))
+        );
+        assert_eq!(
+            ParamType::parse("Node"),
+            ParamType::Unknown("Node".to_string())
+        );
+    }
+
+    #[test]
+    fn incomplete_testcase_names_the_missing_parameters() {
+        let meta_data = meta_data(&[
+            ("nums", "integer[]"),
+            ("target", "integer"),
+            ("k", "integer"),
+        ]);
+        let err = meta_data.validate("[1,2]\n9\n3\n[4]\n\n5\n").unwrap_err();
+        assert_eq!(err, "Incomplete testcase starting at line 4 : missing `k`");
+        assert!(meta_data.validate("[1,2]\n9\n3\n\n[4]\n5\n6").is_ok());
+    }
+
+    #[test]
+    fn integer_array_mismatch() {
+        let meta_data = meta_data(&[("nums", "integer[]")]);
+        assert!(meta_data.validate("[1,2,3]").is_ok());
+        for input in ["[1,\"2\"]", "[1,2.5]", "[2147483648]", "3", "[1,2"] {
+            let err = meta_data.validate(input).unwrap_err();
+            assert!(err.contains("line 1"), "{input} : {err}");
+            assert!(err.contains("`nums`"), "{input} : {err}");
+        }
+    }
+
+    #[test]
+    fn character_grid_mismatch() {
+        let meta_data = meta_data(&[("board", "character[][]")]);
+        assert!(meta_data.validate("[[\"a\",\"b\"],[\"c\",\".\"]]").is_ok());
+        assert!(meta_data.validate("[[\"ab\"]]").is_err());
+        assert!(meta_data.validate("[[1]]").is_err());
+        assert!(meta_data.validate("[\"a\",\"b\"]").is_err());
+    }
+
+    #[test]
+    fn tree_mismatch() {
+        let meta_data = meta_data(&[("root", "TreeNode")]);
+        assert!(meta_data.validate("[3,9,20,null,null,15,7]").is_ok());
+        assert!(meta_data.validate("[]").is_ok());
+        assert!(meta_data.validate("[3,\"9\"]").is_err());
+        assert!(meta_data.validate("3").is_err());
+    }
+
+    #[test]
+    fn string_mismatch() {
+        let meta_data = meta_data(&[("s", "string"), ("k", "integer")]);
+        assert!(meta_data.validate("\"abc\"\n2").is_ok());
+        // strings are quoted in leetcode's input
+        let err = meta_data.validate("abc\n2").unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid testcase input at line 1 : expected string for parameter `s`, found `abc`"
+        );
+        assert!(meta_data.validate("\"abc\"\n\"2\"").is_err());
+    }
+
+    #[test]
+    fn unknown_types_accept_any_value() {
+        let meta_data = meta_data(&[("node", "Node"), ("n", "integer")]);
+        assert!(meta_data.validate("[[2,4],[1,3]]\n1").is_ok());
+        assert!(meta_data.validate("{\"a\": 1}\n1").is_ok());
+        // the input still has to be JSON
+        assert!(meta_data.validate("[[2,4]\n1").is_err());
+    }
+
+    #[test]
+    fn design_questions_take_methods_and_arguments() {
+        let meta_data = MetaData {
+            systemdesign: true,
+            ..Default::default()
+        };
+        assert!(meta_data
+            .validate("[\"LRUCache\",\"put\"]\n[[2],[1,1]]")
+            .is_ok());
+        assert!(meta_data.validate("[\"LRUCache\",1]\n[[2],[1]]").is_err());
+        assert_eq!(
+            meta_data.validate("[\"LRUCache\"]").unwrap_err(),
+            "Incomplete testcase starting at line 1 : missing `arguments`"
+        );
+    }
+}
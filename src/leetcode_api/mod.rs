@@ -1,6 +1,16 @@
+pub mod diff;
+pub mod distribution;
+pub mod event;
 pub mod execution;
 mod helpers;
+pub mod history;
 pub mod leetcode;
+pub mod metadata;
+pub mod nonblocking;
+pub mod policy;
+pub mod problems;
+mod protocol;
 pub mod submission;
 pub mod worker;
-pub mod user;
\ No newline at end of file
+pub mod user;
+pub use helpers::{CodeSnippet, LeetcodeQuestion, QuestionDetails, TopicTag};
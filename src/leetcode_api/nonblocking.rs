@@ -0,0 +1,165 @@
+use super::event::{Event, EventHandler};
+use super::helpers::*;
+use super::leetcode::{Region, Timing};
+use super::metadata::MetaData;
+use super::protocol::*;
+use super::worker::*;
+use crate::file_parser::codefile::CodeFile;
+use crate::file_parser::testcases::Testcases;
+
+/// # Async LeetCode client
+/// Authorized client for async code, made by `LeetCode::authenticate_async`.
+/// Pending results are polled without blocking the thread, so many runs
+/// and submissions can be in flight at once. Clones share the connections.
+///
+/// Needs to run on a tokio runtime, with the time driver enabled.
+/// Testcase files are read with `spawn_blocking`.
+#[derive(Clone)]
+pub struct AsyncLeetCode {
+    pub(super) client: reqwest::Client,
+    pub(super) region: Region,
+    pub(super) timing: Timing,
+    pub(super) events: EventHandler,
+}
+
+impl AsyncLeetCode {
+    /// Full url of a path on the site of the region
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.region.base_url(), path)
+    }
+
+    fn emit(&self, event: Event) {
+        (self.events)(&event);
+    }
+
+    pub async fn question_metadata(&self, title_slug: &str) -> Result<Question, String> {
+        let query = QuestionIdQuery::metadata(title_slug);
+        let Ok(data) = self
+            .client
+            .post(self.url("/graphql"))
+            .json(&query)
+            .send()
+            .await
+        else {
+            return Err("Failed to fetch question id from leetcode".to_string());
+        };
+        data.json::<QuestionData<Question>>()
+            .await
+            .map_err(|_| "Failed to parse question id from leetcode".to_string())
+            .map(|data| data.data.question)
+    }
+
+    pub async fn execute_default(&self, codefile: &CodeFile) -> Result<ExecutionResult, String> {
+        self.execute(codefile, String::new()).await
+    }
+
+    /// # Execute testcases
+    /// Same as `LeetCode::execute`, the default testcases are run
+    /// when no input is given and the batches are combined into one result
+    pub async fn execute(
+        &self,
+        codefile: &CodeFile,
+        data_input: String,
+    ) -> Result<ExecutionResult, String> {
+        let ques = self.question_metadata(&codefile.question_title).await?;
+        let meta_data = meta_data_or_default(&ques, &self.events);
+        // the testcase files are read on the threads meant for blocking
+        let (ques, meta_data, testcases) = tokio::task::spawn_blocking(move || {
+            let testcases = ques.testcases(&meta_data, &data_input);
+            (ques, meta_data, testcases)
+        })
+        .await
+        .map_err(|_| "Failed to read the testcases".to_string())?;
+        self.execute_testcases(codefile, &ques.questionId, &meta_data, &testcases?)
+            .await
+    }
+
+    /// Runs the testcases in batches and combines the results,
+    /// the first batch failing to compile or run is returned as is
+    pub async fn execute_testcases(
+        &self,
+        codefile: &CodeFile,
+        question_id: &str,
+        meta_data: &MetaData,
+        testcases: &Testcases,
+    ) -> Result<ExecutionResult, String> {
+        let mut run = BatchedRun::new(testcases, meta_data, &self.events)?;
+        while let Some(data_input) = run.next_batch() {
+            let result = self
+                .execute_batch(codefile, question_id, data_input)
+                .await?;
+            if let Some(failed) = run.add(result) {
+                return Ok(failed);
+            }
+        }
+        Ok(run.finish())
+    }
+
+    async fn execute_batch(
+        &self,
+        codefile: &CodeFile,
+        question_id: &str,
+        data_input: String,
+    ) -> Result<ExecutionResult, String> {
+        let url = self.url(&interpret_path(codefile));
+        let testcase = TestCaseExec::new(codefile, question_id, data_input);
+        let mut rate_limit = RateLimit::new(self.timing, &self.events);
+        let data = loop {
+            let Ok(data) = self.client.post(&url).json(&testcase).send().await else {
+                return Err(REQUEST_FAILED.to_string());
+            };
+            match rate_limit.retry_after(data.status()) {
+                Some(backoff) => tokio::time::sleep(backoff).await,
+                None => break data,
+            }
+        };
+        let Ok(data) = data.json::<InterpretID>().await else {
+            return Err(PARSE_FAILED.to_string());
+        };
+        self.emit(Event::Executing);
+        self.poll(&data.interpret_id).await
+    }
+
+    pub async fn submit(&self, codefile: &CodeFile) -> Result<SubmissionResult, String> {
+        let ques = self.question_metadata(&codefile.question_title).await?;
+        self.submit_question(codefile, &ques.questionId).await
+    }
+
+    /// Submits the code to the question of the given id, for
+    /// callers which already fetched the metadata of the question
+    pub async fn submit_question(
+        &self,
+        codefile: &CodeFile,
+        question_id: &str,
+    ) -> Result<SubmissionResult, String> {
+        let url = self.url(&submit_path(codefile));
+        let submission = SubmitCode::new(codefile, question_id);
+        let Ok(data) = self.client.post(&url).json(&submission).send().await else {
+            return Err(REQUEST_FAILED.to_string());
+        };
+        let Ok(data) = data.json::<SubmissionID>().await else {
+            return Err(NO_SUBMISSION_ID.to_string());
+        };
+        self.emit(Event::Evaluating);
+        self.poll(&data.submission_id.to_string()).await
+    }
+
+    /// Checks the result of a run or a submission until it isn't pending,
+    /// waiting without blocking in between
+    async fn poll<T: Pollable>(&self, id: &str) -> Result<T, String> {
+        let url = self.url(&check_path(id));
+        let mut poll = Poll::new(&self.events);
+        loop {
+            tokio::time::sleep(self.timing.poll_interval).await;
+            let Ok(data) = self.client.get(&url).send().await else {
+                return Err(REQUEST_FAILED.to_string());
+            };
+            let Ok(data) = data.json::<T>().await else {
+                return Err(PARSE_FAILED.to_string());
+            };
+            if let Some(data) = poll.done(data) {
+                return Ok(data);
+            }
+        }
+    }
+}
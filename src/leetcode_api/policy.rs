@@ -0,0 +1,34 @@
+use super::worker::ExecutionResult;
+use serde::Deserialize;
+
+/// # Submit policy
+/// Decides whether code gets submitted, as per the outcome
+/// of running the testcases beforehand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubmitPolicy {
+    /// Submit only when every testcase passed
+    #[default]
+    WhenCorrect,
+    /// Run the testcases, but submit whatever their outcome
+    Always,
+    /// Submit without running the testcases
+    SkipRun,
+}
+
+impl SubmitPolicy {
+    pub fn runs_testcases(&self) -> bool {
+        *self != SubmitPolicy::SkipRun
+    }
+
+    /// `run` is the outcome of running the testcases,
+    /// `None` when they weren't run
+    pub fn should_submit(&self, run: Option<&Result<ExecutionResult, String>>) -> bool {
+        match self {
+            SubmitPolicy::Always | SubmitPolicy::SkipRun => true,
+            SubmitPolicy::WhenCorrect => {
+                matches!(run, Some(Ok(ExecutionResult::Success(result))) if result.is_correct())
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+/// # Problem list
+/// Every question of the site, as listed by `api/problems/all/`
+#[derive(Debug, Deserialize)]
+pub struct ProblemList {
+    pub stat_status_pairs: Vec<Problem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Problem {
+    pub stat: ProblemStat,
+    /// `ac` when solved, `notac` when attempted
+    pub status: Option<String>,
+    pub difficulty: ProblemDifficulty,
+    pub paid_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProblemStat {
+    pub question_id: u32,
+    pub frontend_question_id: u32,
+    #[serde(rename = "question__title")]
+    pub title: String,
+    #[serde(rename = "question__title_slug")]
+    pub title_slug: String,
+    pub total_acs: u64,
+    pub total_submitted: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProblemDifficulty {
+    pub level: u8,
+}
+
+impl ProblemList {
+    /// Problems ordered by their id as shown on the site
+    pub fn sorted(mut self) -> Vec<Problem> {
+        self.stat_status_pairs
+            .sort_by_key(|problem| problem.stat.frontend_question_id);
+        self.stat_status_pairs
+    }
+}
+
+impl Problem {
+    pub fn difficulty(&self) -> &str {
+        match self.difficulty.level {
+            1 => "Easy",
+            2 => "Medium",
+            3 => "Hard",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.status.as_deref() == Some("ac")
+    }
+}
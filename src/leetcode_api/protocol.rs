@@ -0,0 +1,229 @@
+use super::event::{Event, EventHandler};
+use super::execution::Success;
+use super::helpers::{SubmitCode, TestCaseExec};
+use super::leetcode::Timing;
+use super::metadata::MetaData;
+use super::worker::{ExecutionResult, PendingResult, Question, SubmissionResult};
+use crate::file_parser::codefile::CodeFile;
+use crate::file_parser::testcases::{NamedInput, Testcases};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+pub(super) const REQUEST_FAILED: &str = "Failed to parse arguments";
+pub(super) const PARSE_FAILED: &str =
+    "Failed to parse JSON from leetcode. Try again after sometime or renew cookie";
+pub(super) const NO_SUBMISSION_ID: &str =
+    "Failed to fetch submission id from leetcode. Check your submissions manually on leetcode";
+
+/// Path the testcases of the question are run at
+pub(super) fn interpret_path(codefile: &CodeFile) -> String {
+    format!("/problems/{}/interpret_solution/", codefile.question_title)
+}
+
+/// Path the code of the question is submitted at
+pub(super) fn submit_path(codefile: &CodeFile) -> String {
+    format!("/problems/{}/submit/", codefile.question_title)
+}
+
+/// Path the result of a run or a submission is checked at
+pub(super) fn check_path(id: &str) -> String {
+    format!("/submissions/detail/{id}/check/")
+}
+
+/// MetaData of the question, the default one (which accepts any input)
+/// is reported and used when it can't be parsed
+pub(super) fn meta_data_or_default(question: &Question, events: &EventHandler) -> MetaData {
+    question.meta_data().unwrap_or_else(|_| {
+        events(&Event::Unvalidated);
+        MetaData::default()
+    })
+}
+
+impl TestCaseExec {
+    pub(super) fn new(codefile: &CodeFile, question_id: &str, data_input: String) -> Self {
+        Self {
+            lang: codefile.language.to_string(),
+            question_id: question_id.to_string(),
+            question_title: codefile.question_title.clone(),
+            typed_code: codefile.code.clone(),
+            data_input,
+        }
+    }
+}
+
+impl SubmitCode {
+    pub(super) fn new(codefile: &CodeFile, question_id: &str) -> Self {
+        Self {
+            lang: codefile.language.to_string(),
+            question_id: question_id.to_string(),
+            typed_code: codefile.code.clone(),
+        }
+    }
+}
+
+/// # Batched run
+/// Testcases split into batches small enough for leetcode to accept,
+/// the results of the batches are combined into one as they come.
+pub(super) struct BatchedRun {
+    batches: std::vec::IntoIter<(String, usize)>,
+    inputs: std::vec::IntoIter<NamedInput>,
+    /// Testcases of the batch handed out last
+    batch_size: usize,
+    merged: Option<Success>,
+}
+
+impl BatchedRun {
+    pub(super) fn new(
+        testcases: &Testcases,
+        meta_data: &MetaData,
+        events: &EventHandler,
+    ) -> Result<Self, String> {
+        if testcases.is_empty() {
+            return Err("No testcases found to execute".to_string());
+        }
+        let batches = testcases.batches();
+        if batches.len() > 1 {
+            events(&Event::Batches {
+                testcases: testcases.len(),
+                batches: batches.len(),
+            });
+        }
+        Ok(Self {
+            batches: batches.into_iter(),
+            inputs: testcases.named_inputs(meta_data).into_iter(),
+            batch_size: 0,
+            merged: None,
+        })
+    }
+
+    /// Input of the next batch to run, `None` once all of them ran
+    pub(super) fn next_batch(&mut self) -> Option<String> {
+        let (data_input, batch_size) = self.batches.next()?;
+        self.batch_size = batch_size;
+        Some(data_input)
+    }
+
+    /// Adds the result of the batch handed out last. A batch failing to
+    /// compile or run is returned as is, there is no point running the
+    /// other batches then.
+    pub(super) fn add(&mut self, result: ExecutionResult) -> Option<ExecutionResult> {
+        let ExecutionResult::Success(mut result) = result else {
+            return Some(result);
+        };
+        result.pair_cases(self.inputs.by_ref().take(self.batch_size).collect());
+        match self.merged.as_mut() {
+            Some(merged) => merged.merge(result),
+            None => self.merged = Some(result),
+        }
+        None
+    }
+
+    /// The combined result of every batch
+    pub(super) fn finish(self) -> ExecutionResult {
+        ExecutionResult::Success(self.merged.expect("at least one batch is executed"))
+    }
+}
+
+/// # Rate limit
+/// Counts the retries of a rate limited run
+pub(super) struct RateLimit<'a> {
+    timing: Timing,
+    events: &'a EventHandler,
+    retries: u32,
+}
+
+impl<'a> RateLimit<'a> {
+    pub(super) fn new(timing: Timing, events: &'a EventHandler) -> Self {
+        Self {
+            timing,
+            events,
+            retries: 0,
+        }
+    }
+
+    /// Wait before sending the run again, `None` when the response
+    /// is to be used as is. The backoff grows with every retry.
+    pub(super) fn retry_after(&mut self, status: reqwest::StatusCode) -> Option<Duration> {
+        if status != reqwest::StatusCode::TOO_MANY_REQUESTS
+            || self.retries == self.timing.rate_limit_retries
+        {
+            return None;
+        }
+        self.retries += 1;
+        let backoff = self.timing.rate_limit_backoff * self.retries;
+        (self.events)(&Event::RateLimited {
+            retry_in_secs: backoff.as_secs(),
+        });
+        Some(backoff)
+    }
+}
+
+/// Results which may still be pending while polled
+pub(super) trait Pollable: DeserializeOwned {
+    fn pending(&self) -> Option<&PendingResult>;
+}
+
+impl Pollable for ExecutionResult {
+    fn pending(&self) -> Option<&PendingResult> {
+        match self {
+            ExecutionResult::PendingResult(pending) => Some(pending),
+            _ => None,
+        }
+    }
+}
+
+impl Pollable for SubmissionResult {
+    fn pending(&self) -> Option<&PendingResult> {
+        match self {
+            SubmissionResult::PendingResult(pending) => Some(pending),
+            _ => None,
+        }
+    }
+}
+
+/// # Poll
+/// Follows the states a run or a submission goes through while it is
+/// pending, every new state is reported once
+pub(super) struct Poll<'a> {
+    events: &'a EventHandler,
+    last_state: u8,
+}
+
+impl<'a> Poll<'a> {
+    pub(super) fn new(events: &'a EventHandler) -> Self {
+        Self {
+            events,
+            last_state: 0,
+        }
+    }
+
+    /// The result once it isn't pending anymore
+    pub(super) fn done<T: Pollable>(&mut self, result: T) -> Option<T> {
+        let Some(pending) = result.pending() else {
+            return Some(result);
+        };
+        self.last_state = match pending.state.as_str() {
+            "PENDING" => {
+                if self.last_state == 0 {
+                    (self.events)(&Event::Pending);
+                }
+                1
+            }
+            "STARTED" => {
+                if self.last_state == 1 {
+                    (self.events)(&Event::Started);
+                }
+                2
+            }
+            state => {
+                if self.last_state == 2 {
+                    (self.events)(&Event::UnknownState {
+                        state: state.to_string(),
+                    });
+                }
+                3
+            }
+        };
+        None
+    }
+}
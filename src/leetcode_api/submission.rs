@@ -1,7 +1,8 @@
+use super::diff;
 use colored::Colorize;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SubmitCorrect {
     pub submission_id: String,
     pub lang: String,
@@ -19,7 +20,7 @@ pub struct SubmitCorrect {
     pub memory_percentile: f64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SubmitLimitExceeded {
     pub submission_id: String,
     pub lang: String,
@@ -33,7 +34,7 @@ pub struct SubmitLimitExceeded {
     pub total_testcases: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SubmitWrong {
     pub status_code: u8,
     pub lang: String,
@@ -57,7 +58,7 @@ pub struct SubmitWrong {
     pub input: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SubmitRuntimeError {
     pub status_code: u8,
     pub lang: String,
@@ -81,7 +82,7 @@ pub struct SubmitRuntimeError {
     pub state: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SubmitCompileError {
     pub status_code: u8,
     pub lang: String,
@@ -137,12 +138,13 @@ impl std::fmt::Display for SubmitWrong {
         let seperator = "-------------------------------";
         write!(
             f,
-            "\n{seperator}\n{}\n{seperator}\nStatus : {}\nTestcase {}/{} failed\n\nTestcase failed :\n{}\n",
+            "\n{seperator}\n{}\n{seperator}\nStatus : {}\nTestcase {}/{} failed\n\nTestcase failed :\n{}\n\n{}\n",
             "Submission Wrong!".red().bold(),
             self.status_msg,
              format!("{}",self.total_correct).green(),
               format!("{}",self.total_testcases).green(), 
-              self.last_testcase.cyan()
+              self.last_testcase.cyan(),
+              diff::describe(&self.code_output, &self.expected_output)
         )
     }
 }
@@ -1,8 +1,12 @@
 use super::execution::*;
+use super::metadata::MetaData;
 use super::submission::*;
-use serde::Deserialize;
+use crate::file_parser::testcases::Testcases;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+/// # Submission result
+/// Verdict of the judge on a submitted solution
+#[derive(Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum SubmissionResult {
     Success(SubmitCorrect),
@@ -14,7 +18,10 @@ pub enum SubmissionResult {
     Unknown(Unknown),
 }
 
-#[derive(Deserialize)]
+/// # Execution result
+/// Outcome of running testcases, the results of every
+/// testcase are in `Success::cases`
+#[derive(Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum ExecutionResult {
     Success(Success),
@@ -29,7 +36,8 @@ pub enum ExecutionResult {
 pub enum PendingState {
     Pending,
     Started,
-    Unknown,
+    /// State the client doesn't know of
+    Unknown(String),
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,15 +45,39 @@ pub enum PendingState {
 pub struct Question {
     pub questionId: String,
     pub questionTitle: String,
+    pub titleSlug: String,
     pub exampleTestcaseList: Vec<String>,
+    pub metaData: String,
 }
 
-#[derive(Deserialize)]
+impl Question {
+    pub fn meta_data(&self) -> Result<MetaData, String> {
+        MetaData::parse(&self.metaData)
+    }
+
+    /// Testcases to run for `data_input`. When no input is given,
+    /// the examples of the question are merged with every testcase
+    /// file of the per-problem testcase directory.
+    /// Invalid input is reported here, before anything is sent to leetcode.
+    pub fn testcases(&self, meta_data: &MetaData, data_input: &str) -> Result<Testcases, String> {
+        if !data_input.is_empty() {
+            return Testcases::from_input(data_input, meta_data);
+        }
+        let mut testcases = Testcases {
+            cases: self.exampleTestcaseList.clone(),
+        };
+        testcases.extend(Testcases::from_problem_dir(&self.titleSlug, meta_data)?);
+        Ok(testcases)
+    }
+}
+
+/// Result still being judged once the client stopped polling
+#[derive(Deserialize, Serialize)]
 pub struct PendingResult {
     pub(crate) state: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 pub struct Unknown {}
 
 impl PendingResult {
@@ -53,13 +85,7 @@ impl PendingResult {
         match self.state.as_str() {
             "PENDING" => PendingState::Pending,
             "STARTED" => PendingState::Started,
-            unknown_state => {
-                println!(
-                    "Unknown state : {}\nKindly inform about this to the developer",
-                    unknown_state
-                );
-                PendingState::Unknown
-            }
+            unknown_state => PendingState::Unknown(unknown_state.to_string()),
         }
     }
 }
@@ -0,0 +1,21 @@
+//! # leetcode-runner-cli
+//! Client of leetcode's API along with the parsing of solution files,
+//! what the `leetcode-runner-cli` binary is built on.
+//!
+//! Requests to leetcode don't print anything. Progress of runs and
+//! submissions is reported through [`LeetCode::on_event`], and results
+//! are returned as values. Only the `display` methods of the results
+//! print to stdout, the way the cli shows them.
+//!
+//! [`LeetCode`] is blocking, [`LeetCode::authenticate_async`] makes an
+//! [`AsyncLeetCode`] instead, to run and submit from async code.
+//!
+//! [`LeetCode`]: leetcode_api::leetcode::LeetCode
+//! [`LeetCode::on_event`]: leetcode_api::leetcode::LeetCode::on_event
+//! [`LeetCode::authenticate_async`]: leetcode_api::leetcode::LeetCode::authenticate_async
+//! [`AsyncLeetCode`]: leetcode_api::nonblocking::AsyncLeetCode
+
+/// Solution files, languages and testcases
+pub mod file_parser;
+/// Client of leetcode's API and the types of its responses
+pub mod leetcode_api;
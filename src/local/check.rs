@@ -0,0 +1,458 @@
+use super::runner::build_dir;
+use colored::Colorize;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::language::Language;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+/// Definitions leetcode provides for a language, `ListNode` and `TreeNode`
+/// are only added when the code doesn't define them itself
+struct Stub {
+    prelude: &'static str,
+    list_node: &'static str,
+    tree_node: &'static str,
+}
+
+impl Stub {
+    /// Source of the compilation unit, along with the number of lines before the code
+    fn source(&self, code: &str) -> (String, usize) {
+        let mut source = self.prelude.to_string();
+        if !defines(code, "ListNode") {
+            source.push_str(self.list_node);
+        }
+        if !defines(code, "TreeNode") {
+            source.push_str(self.tree_node);
+        }
+        let stub_lines = source.lines().count();
+        source.push_str(code);
+        (source, stub_lines)
+    }
+}
+
+/// What a line defining a type starts with, in any of the languages
+const DEFINITIONS: [&str; 5] = [
+    "struct ",
+    "class ",
+    "public class ",
+    "type ",
+    "typedef struct ",
+];
+
+/// Whether the code defines the type, commented out definitions aside
+fn defines(code: &str, name: &str) -> bool {
+    code.lines().map(str::trim_start).any(|line| {
+        DEFINITIONS
+            .iter()
+            .filter_map(|keyword| line.strip_prefix(keyword)?.strip_prefix(name))
+            .any(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+    })
+}
+
+// Behind a glob import, which the imports and definitions of the code
+// shadow rather than collide with. Snippets of tree problems import
+// `Rc` and `RefCell` themselves.
+const RUST_STUB: Stub = Stub {
+    prelude: "#![allow(dead_code, unused_imports)]
+mod __lc_stub {
+    pub use std::cell::RefCell;
+    pub use std::cmp::{max, min, Ordering, Reverse};
+    pub use std::collections::*;
+    pub use std::rc::Rc;
+    #[derive(PartialEq, Eq, Clone, Debug)]
+    pub struct ListNode {
+        pub val: i32,
+        pub next: Option<Box<ListNode>>,
+    }
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct TreeNode {
+        pub val: i32,
+        pub left: Option<Rc<RefCell<TreeNode>>>,
+        pub right: Option<Rc<RefCell<TreeNode>>>,
+    }
+}
+use __lc_stub::*;
+struct Solution;
+",
+    list_node: "",
+    tree_node: "",
+};
+
+const CPP_STUB: Stub = Stub {
+    prelude: "#include <bits/stdc++.h>
+using namespace std;
+",
+    list_node: "struct ListNode {
+    int val;
+    ListNode *next;
+    ListNode() : val(0), next(nullptr) {}
+    ListNode(int x) : val(x), next(nullptr) {}
+    ListNode(int x, ListNode *next) : val(x), next(next) {}
+};
+",
+    tree_node: "struct TreeNode {
+    int val;
+    TreeNode *left;
+    TreeNode *right;
+    TreeNode() : val(0), left(nullptr), right(nullptr) {}
+    TreeNode(int x) : val(x), left(nullptr), right(nullptr) {}
+    TreeNode(int x, TreeNode *left, TreeNode *right) : val(x), left(left), right(right) {}
+};
+",
+};
+
+const C_STUB: Stub = Stub {
+    prelude: "#include <limits.h>
+#include <math.h>
+#include <stdbool.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+",
+    list_node: "struct ListNode {
+    int val;
+    struct ListNode *next;
+};
+",
+    tree_node: "struct TreeNode {
+    int val;
+    struct TreeNode *left;
+    struct TreeNode *right;
+};
+",
+};
+
+const JAVA_STUB: Stub = Stub {
+    prelude: "import java.util.*;
+import java.util.stream.*;
+",
+    list_node: "class ListNode {
+    int val;
+    ListNode next;
+    ListNode() {}
+    ListNode(int val) { this.val = val; }
+    ListNode(int val, ListNode next) { this.val = val; this.next = next; }
+}
+",
+    tree_node: "class TreeNode {
+    int val;
+    TreeNode left;
+    TreeNode right;
+    TreeNode() {}
+    TreeNode(int val) { this.val = val; }
+    TreeNode(int val, TreeNode left, TreeNode right) { this.val = val; this.left = left; this.right = right; }
+}
+",
+};
+
+const PYTHON_STUB: Stub = Stub {
+    prelude: "from typing import *
+from collections import *
+from functools import *
+from itertools import *
+from heapq import *
+from bisect import *
+import math
+",
+    list_node: "class ListNode:
+    def __init__(self, val=0, next=None):
+        self.val = val
+        self.next = next
+",
+    tree_node: "class TreeNode:
+    def __init__(self, val=0, left=None, right=None):
+        self.val = val
+        self.left = left
+        self.right = right
+",
+};
+
+const GO_STUB: Stub = Stub {
+    prelude: "package main
+",
+    list_node: "type ListNode struct {
+	Val  int
+	Next *ListNode
+}
+",
+    tree_node: "type TreeNode struct {
+	Val   int
+	Left  *TreeNode
+	Right *TreeNode
+}
+",
+};
+
+const NO_STUB: Stub = Stub {
+    prelude: "",
+    list_node: "",
+    tree_node: "",
+};
+
+/// # Local compile check
+/// Outcome of compiling the extracted code locally,
+/// diagnostics point to the lines of the original file.
+#[derive(Serialize)]
+pub struct CompileCheck {
+    pub success: bool,
+    pub diagnostics: String,
+}
+
+impl CompileCheck {
+    pub fn display(&self) {
+        println!("{}", self);
+    }
+}
+
+/// Compiles the extracted code along with the definitions leetcode
+/// provides for the language (`struct Solution;`, usual imports, ...).
+/// Nothing is run, only compiler diagnostics are collected.
+pub fn check(codefile: &CodeFile) -> Result<CompileCheck, String> {
+    let build_dir = build_dir();
+    std::fs::create_dir_all(&build_dir)
+        .map_err(|_| format!("Failed to create build directory {}", build_dir.display()))?;
+    let result = check_in(codefile, &build_dir);
+    let _ = std::fs::remove_dir_all(&build_dir);
+    result
+}
+
+fn check_in(codefile: &CodeFile, build_dir: &Path) -> Result<CompileCheck, String> {
+    let language = &codefile.language;
+    let source_name = match language {
+        // a public class has to be in a file of the same name
+        Language::Java => "Solution.java".to_string(),
+        language => format!("solution.{}", language.extension()),
+    };
+    let source = build_dir.join(source_name);
+    let source_str = source.display().to_string();
+    let output = build_dir.join("solution").display().to_string();
+    let build_dir_str = build_dir.display().to_string();
+
+    let (stub, args): (Stub, Vec<&str>) = match language {
+        Language::Rust => (
+            RUST_STUB,
+            vec![
+                "rustc",
+                "--edition",
+                "2021",
+                "--crate-type",
+                "lib",
+                "--emit=metadata",
+                "--error-format=short",
+                "-o",
+                &output,
+                &source_str,
+            ],
+        ),
+        Language::Cpp => (
+            CPP_STUB,
+            vec!["g++", "-std=c++17", "-fsyntax-only", &source_str],
+        ),
+        Language::C => (C_STUB, vec!["gcc", "-fsyntax-only", &source_str]),
+        Language::Java => (JAVA_STUB, vec!["javac", "-d", &build_dir_str, &source_str]),
+        Language::Python3 => (
+            PYTHON_STUB,
+            vec!["python3", "-m", "py_compile", &source_str],
+        ),
+        Language::Javascript => (NO_STUB, vec!["node", "--check", &source_str]),
+        // without the imports leetcode adds, only the syntax can be checked
+        Language::Go => (GO_STUB, vec!["gofmt", "-e", &source_str]),
+        language => {
+            return Err(format!(
+                "Local compile check is not supported for {}",
+                language
+            ))
+        }
+    };
+
+    let (contents, stub_lines) = stub.source(&codefile.code);
+    std::fs::write(&source, contents)
+        .map_err(|_| format!("Failed to write {}", source.display()))?;
+    let compile = Command::new(args[0])
+        .args(&args[1..])
+        .output()
+        .map_err(|_| {
+            format!(
+                "Failed to execute {}, make sure it is installed and in PATH",
+                args[0]
+            )
+        })?;
+
+    // every supported compiler reports on stderr
+    let diagnostics = String::from_utf8_lossy(&compile.stderr);
+    Ok(CompileCheck {
+        success: compile.status.success(),
+        diagnostics: remap_lines(&diagnostics, &source_str, codefile, stub_lines),
+    })
+}
+
+/// Rewrites `<source>:<line>` and `"<source>", line <line>` references
+/// of the compilation unit to the path and line of the original file
+fn remap_lines(diagnostics: &str, source: &str, codefile: &CodeFile, stub_lines: usize) -> String {
+    let mut pieces = diagnostics.split(source);
+    let mut remapped = pieces.next().unwrap_or_default().to_string();
+    for piece in pieces {
+        let (separator, rest) = if let Some(rest) = piece.strip_prefix("\", line ") {
+            ("\", line ", rest)
+        } else if let Some(rest) = piece.strip_prefix(':') {
+            (":", rest)
+        } else {
+            remapped.push_str(source);
+            remapped.push_str(piece);
+            continue;
+        };
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        match rest[..digits].parse::<usize>() {
+            Ok(line) if line > stub_lines => {
+                remapped.push_str(&codefile.path.display().to_string());
+                remapped.push_str(separator);
+                remapped.push_str(&codefile.file_line(line - stub_lines).to_string());
+                remapped.push_str(&rest[digits..]);
+            }
+            // references to the stub are left alone
+            _ => {
+                remapped.push_str(source);
+                remapped.push_str(piece);
+            }
+        }
+    }
+    remapped
+}
+
+impl std::fmt::Display for CompileCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.success {
+            write!(f, "{}", "Local compile check passed".green().bold())?;
+        } else {
+            write!(f, "{}", "Local compile check failed".red().bold())?;
+        }
+        if !self.diagnostics.trim().is_empty() {
+            write!(f, "\n\n{}", self.diagnostics.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn codefile(language: Language, code: &str) -> CodeFile {
+        CodeFile {
+            language,
+            path: PathBuf::from("solution"),
+            question_title: "maximum-depth-of-binary-tree".to_string(),
+            code: code.to_string(),
+            line_offset: 0,
+        }
+    }
+
+    /// Checks in a directory of its own, tests run concurrently
+    fn check_code(language: Language, code: &str, name: &str) -> CompileCheck {
+        let build_dir = std::env::temp_dir().join(format!(
+            "leetcode-runner-cli-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::create_dir_all(&build_dir).unwrap();
+        let result = check_in(&codefile(language, code), &build_dir);
+        let _ = std::fs::remove_dir_all(&build_dir);
+        result.unwrap()
+    }
+
+    // as given by leetcode, imports included
+    const RUST_TREE_SNIPPET: &str = "// Definition for a binary tree node.
+// #[derive(Debug, PartialEq, Eq)]
+// pub struct TreeNode {
+//   pub val: i32,
+//   pub left: Option<Rc<RefCell<TreeNode>>>,
+//   pub right: Option<Rc<RefCell<TreeNode>>>,
+// }
+use std::rc::Rc;
+use std::cell::RefCell;
+impl Solution {
+    pub fn max_depth(root: Option<Rc<RefCell<TreeNode>>>) -> i32 {
+        match root {
+            Some(node) => {
+                let node = node.borrow();
+                1 + max(Self::max_depth(node.left.clone()), Self::max_depth(node.right.clone()))
+            }
+            None => 0,
+        }
+    }
+}
+";
+
+    #[test]
+    fn rust_tree_snippet_compiles() {
+        let check = check_code(Language::Rust, RUST_TREE_SNIPPET, "rust-tree");
+        assert!(check.success, "{}", check.diagnostics);
+    }
+
+    #[test]
+    fn rust_errors_point_to_the_code() {
+        let check = check_code(
+            Language::Rust,
+            "fn f() -> i32 {\n    \"\"\n}\n",
+            "rust-error",
+        );
+        assert!(!check.success);
+        assert!(
+            check.diagnostics.contains("solution:2:"),
+            "{}",
+            check.diagnostics
+        );
+    }
+
+    #[test]
+    fn cpp_own_tree_node_compiles() {
+        let code = "struct TreeNode {
+    int val;
+    TreeNode *left, *right;
+};
+class Solution {
+public:
+    int maxDepth(TreeNode* root) {
+        return root ? 1 + max(maxDepth(root->left), maxDepth(root->right)) : 0;
+    }
+};
+";
+        let check = check_code(Language::Cpp, code, "cpp-tree");
+        assert!(check.success, "{}", check.diagnostics);
+    }
+
+    #[test]
+    fn java_own_list_node_compiles() {
+        let code = "class ListNode {
+    int val;
+    ListNode next;
+}
+class Solution {
+    public int length(ListNode head) {
+        return head == null ? 0 : 1 + length(head.next);
+    }
+}
+";
+        let check = check_code(Language::Java, code, "java-list");
+        assert!(check.success, "{}", check.diagnostics);
+    }
+
+    #[test]
+    fn commented_definitions_are_not_definitions() {
+        let code =
+            "/**\n * struct TreeNode {\n * };\n */\n// struct ListNode {\nstruct TreeNodes {};";
+        assert!(!defines(code, "TreeNode"));
+        assert!(!defines(code, "ListNode"));
+        assert!(defines("  public class ListNode {", "ListNode"));
+        assert!(defines("type TreeNode struct {", "TreeNode"));
+    }
+
+    #[test]
+    fn stub_lines_count_what_is_added() {
+        let (source, stub_lines) = CPP_STUB.source("struct ListNode {};\n");
+        assert_eq!(source.lines().nth(stub_lines), Some("struct ListNode {};"));
+        assert!(!source.contains("ListNode *next"));
+        assert!(source.contains("TreeNode *left"));
+    }
+}
@@ -0,0 +1,337 @@
+use colored::Colorize;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::language::Language;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Time the program may run for before it gets killed
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Delay between two checks of whether the program exited
+const WAIT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// # Local toolchain
+/// Commands to build and run a source file with the toolchain
+/// installed on the machine.
+pub struct Toolchain {
+    /// Command building `source` into the build directory,
+    /// `None` for interpreted languages
+    compile: Option<Vec<String>>,
+    run: Vec<String>,
+}
+
+impl Toolchain {
+    pub fn new(language: &Language, source: &Path, build_dir: &Path) -> Result<Self, String> {
+        let source = source.display().to_string();
+        let binary = build_dir.join("main").display().to_string();
+        let build_dir = build_dir.display().to_string();
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+        let toolchain = match language {
+            Language::Rust => Self {
+                compile: Some(args(&[
+                    "rustc",
+                    "--edition",
+                    "2021",
+                    "-O",
+                    "-o",
+                    &binary,
+                    &source,
+                ])),
+                run: args(&[&binary]),
+            },
+            Language::Cpp => Self {
+                compile: Some(args(&["g++", "-std=c++17", "-O2", "-o", &binary, &source])),
+                run: args(&[&binary]),
+            },
+            Language::C => Self {
+                compile: Some(args(&["gcc", "-O2", "-o", &binary, &source, "-lm"])),
+                run: args(&[&binary]),
+            },
+            Language::Go => Self {
+                compile: Some(args(&["go", "build", "-o", &binary, &source])),
+                run: args(&[&binary]),
+            },
+            Language::Java => {
+                // the public class is named after the file
+                let class = Path::new(&source)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or("Main")
+                    .to_string();
+                Self {
+                    compile: Some(args(&["javac", "-d", &build_dir, &source])),
+                    run: args(&["java", "-cp", &build_dir, &class]),
+                }
+            }
+            Language::Python3 => Self {
+                compile: None,
+                run: args(&["python3", &source]),
+            },
+            Language::Javascript => Self {
+                compile: None,
+                run: args(&["node", &source]),
+            },
+            language => return Err(format!("Local execution is not supported for {}", language)),
+        };
+        Ok(toolchain)
+    }
+}
+
+/// # Local run result
+pub enum LocalResult {
+    CompileError {
+        status: ExitStatus,
+        stderr: String,
+        compile_time: Duration,
+    },
+    Ran(LocalRun),
+}
+
+pub struct LocalRun {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+    pub compile_time: Option<Duration>,
+    pub run_time: Duration,
+    /// Killed for running longer than the timeout
+    pub timed_out: bool,
+}
+
+impl LocalResult {
+    pub fn is_success(&self) -> bool {
+        matches!(self, LocalResult::Ran(run) if run.status.success() && !run.timed_out)
+    }
+    pub fn display(&self) {
+        println!("{}", self);
+    }
+}
+
+/// Compiles and runs the whole code file, driver code included.
+/// `input` is fed to the standard input of the program, which is
+/// killed once it runs for longer than `timeout`.
+pub fn run(codefile: &CodeFile, input: &str, timeout: Duration) -> Result<LocalResult, String> {
+    let build_dir = build_dir();
+    std::fs::create_dir_all(&build_dir)
+        .map_err(|_| format!("Failed to create build directory {}", build_dir.display()))?;
+    let result = run_in(codefile, input, timeout, &build_dir);
+    let _ = std::fs::remove_dir_all(&build_dir);
+    result
+}
+
+fn run_in(
+    codefile: &CodeFile,
+    input: &str,
+    timeout: Duration,
+    build_dir: &Path,
+) -> Result<LocalResult, String> {
+    let toolchain = Toolchain::new(&codefile.language, &codefile.path, build_dir)?;
+
+    let mut compile_time = None;
+    if let Some(compile) = &toolchain.compile {
+        println!("Compiling {}...", codefile.path.display());
+        let start = Instant::now();
+        let output = command(compile)
+            .output()
+            .map_err(|_| not_found(&compile[0]))?;
+        let elapsed = start.elapsed();
+        if !output.status.success() {
+            return Ok(LocalResult::CompileError {
+                status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                compile_time: elapsed,
+            });
+        }
+        compile_time = Some(elapsed);
+    }
+
+    println!("Running {}...", codefile.path.display());
+    let start = Instant::now();
+    let mut child = command(&toolchain.run)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| not_found(&toolchain.run[0]))?;
+    // every pipe is served by a thread of its own, a program filling its
+    // output before reading all of its input would block forever otherwise
+    let stdin = child.stdin.take().map(|mut stdin| {
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            // the program may exit without reading its input
+            let _ = stdin.write_all(input.as_bytes());
+        })
+    });
+    let stdout = child.stdout.take().map(read_pipe);
+    let stderr = child.stderr.take().map(read_pipe);
+
+    let failed = || format!("Failed to run {}", codefile.path.display());
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait().map_err(|_| failed())? {
+            Some(status) => break status,
+            None if start.elapsed() >= timeout => {
+                timed_out = true;
+                let _ = child.kill();
+                break child.wait().map_err(|_| failed())?;
+            }
+            None => std::thread::sleep(WAIT_INTERVAL),
+        }
+    };
+    let run_time = start.elapsed();
+    if let Some(stdin) = stdin {
+        let _ = stdin.join();
+    }
+    let output = |pipe: Option<JoinHandle<String>>| {
+        pipe.and_then(|pipe| pipe.join().ok()).unwrap_or_default()
+    };
+    Ok(LocalResult::Ran(LocalRun {
+        status,
+        stdout: output(stdout),
+        stderr: output(stderr),
+        compile_time,
+        run_time,
+        timed_out,
+    }))
+}
+
+/// Reads the pipe until the program closes it, on another thread
+fn read_pipe(mut pipe: impl Read + Send + 'static) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut output = vec![];
+        let _ = pipe.read_to_end(&mut output);
+        String::from_utf8_lossy(&output).to_string()
+    })
+}
+
+/// Per process build directory inside the system temp directory
+pub fn build_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("leetcode-runner-cli-{}", std::process::id()))
+}
+
+fn command(args: &[String]) -> Command {
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]);
+    command
+}
+
+fn not_found(program: &str) -> String {
+    format!(
+        "Failed to execute {}, make sure it is installed and in PATH",
+        program
+    )
+}
+
+fn exit_status(status: &ExitStatus) -> String {
+    match status.code() {
+        Some(code) => code.to_string(),
+        None => "killed by signal".to_string(),
+    }
+}
+
+impl std::fmt::Display for LocalResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seperator = "-------------------------------";
+        match self {
+            LocalResult::CompileError {
+                status,
+                stderr,
+                compile_time,
+            } => write!(
+                f,
+                "\n{seperator}\n{}\n{seperator}\nExit status  : {}\nCompile time : {:.2?}\n\n{}",
+                "Compile Error!".red().bold(),
+                exit_status(status),
+                compile_time,
+                stderr
+            ),
+            LocalResult::Ran(run) => {
+                write!(
+                    f,
+                    "\n{seperator}\n{}\n{seperator}\nExit status  : {}\n",
+                    if run.timed_out {
+                        "Time Limit Exceeded".red().bold()
+                    } else if run.status.success() {
+                        "Local execution success".green().bold()
+                    } else {
+                        "Local execution failed".red().bold()
+                    },
+                    if run.timed_out {
+                        "killed after the timeout".to_string()
+                    } else {
+                        exit_status(&run.status)
+                    }
+                )?;
+                if let Some(compile_time) = run.compile_time {
+                    writeln!(f, "Compile time : {:.2?}", compile_time)?;
+                }
+                writeln!(
+                    f,
+                    "Run time     : {}",
+                    format!("{:.2?}", run.run_time).cyan()
+                )?;
+                if !run.stdout.is_empty() {
+                    write!(f, "\nStd Output :\n{}", run.stdout)?;
+                }
+                if !run.stderr.is_empty() {
+                    write!(f, "\nStd Error :\n{}", run.stderr)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs in a directory of its own, tests run concurrently
+    fn run_code(code: &str, input: &str, timeout: Duration, name: &str) -> LocalResult {
+        let build_dir = std::env::temp_dir().join(format!(
+            "leetcode-runner-cli-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::create_dir_all(&build_dir).unwrap();
+        let path = build_dir.join("solution.py");
+        std::fs::write(&path, code).unwrap();
+        let codefile = CodeFile {
+            language: Language::Python3,
+            path,
+            question_title: "two-sum".to_string(),
+            code: code.to_string(),
+            line_offset: 0,
+        };
+        let result = run_in(&codefile, input, timeout, &build_dir);
+        let _ = std::fs::remove_dir_all(&build_dir);
+        result.unwrap()
+    }
+
+    #[test]
+    fn output_larger_than_the_pipe_before_reading_the_input() {
+        // fills stdout before touching stdin, both beyond a pipe buffer
+        let code = "import sys\nsys.stdout.write('x' * 1_000_000)\nsys.stdout.flush()\nprint(len(sys.stdin.read()))\n";
+        let input = "y".repeat(1_000_000);
+        let LocalResult::Ran(run) = run_code(code, &input, DEFAULT_TIMEOUT, "pipes") else {
+            panic!("python doesn't compile");
+        };
+        assert!(run.status.success());
+        assert!(!run.timed_out);
+        assert_eq!(run.stdout.len(), 1_000_000 + "1000000\n".len());
+    }
+
+    #[test]
+    fn killed_after_the_timeout() {
+        let code = "print('started', flush=True)\nwhile True:\n    pass\n";
+        let result = run_code(code, "", Duration::from_millis(500), "timeout");
+        assert!(!result.is_success());
+        let LocalResult::Ran(run) = result else {
+            panic!("python doesn't compile");
+        };
+        assert!(run.timed_out);
+        assert_eq!(run.stdout, "started\n");
+        assert!(run.run_time < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::config::{self, Config};
+use crate::local::check;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::leetcode_api::event::Event;
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Any error of leetcode, the files or the compiler
+const SERVER_ERROR: i64 = -32000;
+const NOT_AUTHENTICATED: i64 = -32001;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        Self::new(SERVER_ERROR, message)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AuthenticateParams {
+    /// Falls back to `LC_COOKIE`
+    cookie: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QuestionParams {
+    title_slug: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScaffoldParams {
+    title_slug: String,
+    /// Falls back to the configured language
+    language: Option<String>,
+    /// Falls back to the configured file template
+    path: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunParams {
+    path: PathBuf,
+    /// Testcases in the format of the testcase files,
+    /// the default testcases are run when missing
+    testcases: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileParams {
+    path: PathBuf,
+}
+
+/// # JSON-RPC server
+/// JSON-RPC 2.0 over stdio, one message per line. Requests are handled
+/// one at a time, the progress of a request is sent as `progress`
+/// notifications before its response.
+struct Server<'a> {
+    config: &'a Config,
+    lc: Option<LeetCode<Authorized>>,
+    /// Id of the request being handled, progress notifications refer to it
+    request: Arc<Mutex<Value>>,
+    shutdown: bool,
+}
+
+/// Serves requests from stdin until `shutdown` or end of input
+pub fn run(config: &Config) -> Result<(), String> {
+    let mut server = Server {
+        config,
+        lc: None,
+        request: Arc::new(Mutex::new(Value::Null)),
+        shutdown: false,
+    };
+    for line in std::io::stdin().lock().lines() {
+        let line = line.map_err(|e| format!("Failed to read request : {e}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle(&line) {
+            send(&response);
+        }
+        if server.shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn send(message: &Value) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", message);
+    let _ = stdout.flush();
+}
+
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    // methods without any required param may be called without params
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn to_value<T: serde::Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::from(e.to_string()))
+}
+
+impl Server<'_> {
+    /// Response to a request, none for notifications
+    fn handle(&mut self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = RpcError::new(PARSE_ERROR, e.to_string());
+                return Some(response(Value::Null, Err(error)));
+            }
+        };
+        let id = request.get("id").cloned();
+        let method = request.get("method").and_then(Value::as_str);
+        let (Some(method), Some("2.0")) = (method, request["jsonrpc"].as_str()) else {
+            let error = RpcError::new(INVALID_REQUEST, "Not a JSON-RPC 2.0 request");
+            return Some(response(id.unwrap_or(Value::Null), Err(error)));
+        };
+
+        *self.request.lock().unwrap() = id.clone().unwrap_or(Value::Null);
+        let result = self.call(method, request["params"].clone());
+        id.map(|id| response(id, result))
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "authenticate" => self.authenticate(self::params(params)?),
+            "question" => self.question(self::params(params)?),
+            "scaffold" => self.scaffold(self::params(params)?),
+            "run" => self.run(self::params(params)?),
+            "submit" => self.submit(self::params(params)?),
+            "check" => self.check(self::params(params)?),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            unknown => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method {}", unknown),
+            )),
+        }
+    }
+
+    fn lc(&self) -> Result<&LeetCode<Authorized>, RpcError> {
+        self.lc
+            .as_ref()
+            .ok_or_else(|| RpcError::new(NOT_AUTHENTICATED, "Call authenticate first"))
+    }
+
+    fn authenticate(&mut self, params: AuthenticateParams) -> Result<Value, RpcError> {
+        let cookie = match params.cookie {
+            Some(cookie) => cookie,
+            None => std::env::var("LC_COOKIE")
+                .map_err(|_| "No cookie given and LC_COOKIE is not set".to_string())?,
+        };
+        let request = self.request.clone();
+        let lc = LeetCode::new()
+            .region(self.config.region.unwrap_or_default())
+            .timing(self.config.timing())
+            .on_event(move |event| progress(&request, event))
+            .authenticate(&cookie)?;
+        let metadata = lc.get_metadata()?;
+        self.lc = Some(lc);
+        to_value(metadata)
+    }
+
+    /// Details of the question, along with the names of its parameters
+    fn question(&self, params: QuestionParams) -> Result<Value, RpcError> {
+        let lc = self.lc()?;
+        let details = lc.question_details(&params.title_slug)?;
+        let question = lc.question_metadata(&params.title_slug)?;
+        let meta_data = lc.meta_data(&question);
+        let mut value = to_value(details)?;
+        value["params"] = to_value(meta_data.param_names())?;
+        Ok(value)
+    }
+
+    /// Writes the solution file with the starting code, unless it exists
+    fn scaffold(&self, params: ScaffoldParams) -> Result<Value, RpcError> {
+        let language = match params.language {
+            Some(name) => config::parse_language(&name).ok_or_else(|| {
+                RpcError::new(INVALID_PARAMS, format!("Unsupported language {}", name))
+            })?,
+            None => self.config.language().unwrap_or_default(),
+        };
+        let path = params
+            .path
+            .unwrap_or_else(|| PathBuf::from(self.config.file_name(&params.title_slug, &language)));
+        let existed = path.exists();
+        let details = self.lc()?.question_details(&params.title_slug)?;
+        let snippet = details.snippet(&language).unwrap_or_default();
+        let region = self.config.region.unwrap_or_default();
+        CodeFile::scaffold(&path, &language, region, &params.title_slug, snippet)?;
+        Ok(json!({ "path": path, "created": !existed }))
+    }
+
+    fn run(&self, params: RunParams) -> Result<Value, RpcError> {
+        let lc = self.lc()?;
+        let code = CodeFile::try_from_file(&params.path)?;
+        to_value(lc.execute(&code, params.testcases.unwrap_or_default())?)
+    }
+
+    fn submit(&self, params: FileParams) -> Result<Value, RpcError> {
+        let lc = self.lc()?;
+        let code = CodeFile::try_from_file(&params.path)?;
+        to_value(lc.submit(&code)?)
+    }
+
+    /// Compiles the solution locally, doesn't need authentication
+    fn check(&self, params: FileParams) -> Result<Value, RpcError> {
+        let code = CodeFile::try_from_file(&params.path)?;
+        to_value(check::check(&code)?)
+    }
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+/// Sends the event as a `progress` notification of the current request
+fn progress(request: &Mutex<Value>, event: &Event) {
+    let mut params = json!(event);
+    params["request"] = request.lock().map(|id| id.clone()).unwrap_or_default();
+    params["message"] = json!(event.to_string());
+    send(&json!({ "jsonrpc": "2.0", "method": "progress", "params": params }));
+}
@@ -0,0 +1,531 @@
+use std::collections::HashMap;
+
+/// Inclusive range of values allowed by a constraint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: i64,
+    pub max: i64,
+}
+
+/// # Constraint range
+/// Inclusive range whose ends are expressions, which may refer to other
+/// parameters, e.g. `1 <= k <= nums.length`. Known once the parameters
+/// it refers to are generated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub min: String,
+    pub max: String,
+}
+
+impl Range {
+    /// A single value, e.g. the `nums.length` of `n == nums.length`
+    fn exactly(expr: &str) -> Self {
+        Self {
+            min: expr.to_string(),
+            max: expr.to_string(),
+        }
+    }
+
+    /// Evaluates the ends with the values of the parameters generated so far,
+    /// `None` when it refers to a parameter which isn't
+    pub fn bounds(&self, known: &HashMap<String, i64>) -> Option<Bounds> {
+        Some(Bounds {
+            min: eval(&self.min, known)?,
+            max: eval(&self.max, known)?,
+        })
+    }
+
+    /// Parameters the range refers to
+    pub fn references(&self) -> Vec<String> {
+        let mut references = identifiers(&self.min);
+        references.extend(identifiers(&self.max));
+        references
+            .iter()
+            .map(|name| name.split(['.', '[']).next().unwrap_or(name).to_string())
+            .collect()
+    }
+}
+
+/// # Question constraints
+/// Bounds parsed from the `Constraints:` section of the question content.
+///
+/// Keys are the parameter names as written in the question. Nested
+/// elements have their indices dropped, e.g. the length of `words[i]`
+/// is kept under `words[]`. Lengths are referred to as `words[].length`.
+#[derive(Debug, Default)]
+pub struct Constraints {
+    /// Bounds of plain values, e.g. `1 <= k <= 10^4`
+    pub values: HashMap<String, Range>,
+    /// Bounds of the elements, e.g. `-10^9 <= nums[i] <= 10^9` or `Node.val`
+    pub elements: HashMap<String, Range>,
+    /// Bounds of the lengths, e.g. `1 <= s.length <= 100`
+    pub lengths: HashMap<String, Range>,
+    /// Characters a string is made of, e.g. `s consists of lowercase English letters.`
+    pub alphabets: HashMap<String, Vec<char>>,
+    /// Number of nodes of tree and linked list parameters
+    pub nodes: Option<Bounds>,
+}
+
+impl Constraints {
+    /// Parses the constraints from the HTML content of a question.
+    /// Constraints which can't be understood are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut constraints = Self::default();
+        let Some(start) = content.find("Constraints:") else {
+            return constraints;
+        };
+        let section = &content[start..];
+        let section = &section[..section.find("</ul>").unwrap_or(section.len())];
+
+        let mut aliases = vec![];
+        for item in section.split("<li>").skip(1) {
+            let item = normalize(&item[..item.find("</li>").unwrap_or(item.len())]);
+            if item.contains("number of nodes") {
+                constraints.nodes = parse_range(&item).or(constraints.nodes);
+            } else if item.contains("consist") {
+                constraints.parse_alphabet(&item);
+            } else if item.contains('<') {
+                constraints.parse_bounds(&item);
+            } else if let Some((lhs, rhs)) = item.trim_end_matches('.').split_once("==") {
+                aliases.push((lhs.trim().to_string(), rhs.trim().to_string()));
+            }
+        }
+
+        // `n == nums.length` along with `1 <= n <= 100`, the bounds of `n`
+        // are those of the length and `n` is whatever length is generated
+        for (lhs, rhs) in aliases {
+            let (name, length) = match (lhs.ends_with(".length"), rhs.ends_with(".length")) {
+                (false, true) => (lhs, rhs),
+                (true, false) => (rhs, lhs),
+                _ => continue,
+            };
+            let length = strip_indices(&length);
+            let length_of = length.trim_end_matches(".length").to_string();
+            if let Some(range) = constraints.values.get(&name).cloned() {
+                constraints.lengths.entry(length_of).or_insert(range);
+            }
+            constraints.values.insert(name, Range::exactly(&length));
+        }
+        constraints
+    }
+
+    /// `lo <= a, b <= hi` style constraints, chains like `1 <= k <= n <= 10^5`
+    /// bound every name by the first value below it and the next one above
+    fn parse_bounds(&mut self, item: &str) {
+        let mut parts = vec![];
+        let mut strict = vec![];
+        let mut rest = item;
+        while let Some(idx) = rest.find('<') {
+            parts.push(rest[..idx].trim());
+            if rest[idx + 1..].starts_with('=') {
+                strict.push(false);
+                rest = &rest[idx + 2..];
+            } else {
+                strict.push(true);
+                rest = &rest[idx + 1..];
+            }
+        }
+        parts.push(rest.trim().trim_end_matches('.'));
+        if parts.len() < 3 || !is_expr(parts[0]) {
+            return;
+        }
+        let lo = parts[0];
+        for idx in 1..parts.len() - 1 {
+            let hi = parts[idx + 1];
+            if !is_expr(hi) {
+                continue;
+            }
+            let bounds = Range {
+                min: if strict[0] {
+                    format!("{lo} + 1")
+                } else {
+                    lo.to_string()
+                },
+                max: if strict[idx] {
+                    format!("{hi} - 1")
+                } else {
+                    hi.to_string()
+                },
+            };
+            self.insert_bounds(parts[idx], bounds);
+        }
+    }
+
+    fn insert_bounds(&mut self, names: &str, bounds: Range) {
+        for name in names.split(',').map(str::trim) {
+            if let Some(length_of) = name
+                .strip_suffix(".length")
+                .or_else(|| name.strip_suffix(".size"))
+            {
+                self.lengths
+                    .insert(strip_indices(length_of), bounds.clone());
+            } else if name.contains('[') || name.contains('.') {
+                let base = name.split(['[', '.']).next().unwrap_or(name);
+                let base = if base == "Node" { "Node.val" } else { base };
+                self.elements.insert(base.to_string(), bounds.clone());
+            } else if !name.is_empty() {
+                self.values.insert(name.to_string(), bounds.clone());
+            }
+        }
+    }
+
+    /// `s consists of lowercase English letters and digits.`
+    fn parse_alphabet(&mut self, item: &str) {
+        let Some(name) = item.split_whitespace().next() else {
+            return;
+        };
+        let description = item.to_lowercase();
+        let mut alphabet = vec![];
+        let lowercase = description.contains("lowercase");
+        let uppercase = description.contains("uppercase");
+        if lowercase || (!uppercase && description.contains("english letters")) {
+            alphabet.extend('a'..='z');
+        }
+        if uppercase || (!lowercase && description.contains("english letters")) {
+            alphabet.extend('A'..='Z');
+        }
+        if description.contains("digit") {
+            alphabet.extend('0'..='9');
+        }
+        // characters listed explicitly, e.g. `only '(' and ')'`
+        if alphabet.is_empty() {
+            alphabet.extend(item.split('\'').skip(1).step_by(2).filter_map(|quoted| {
+                let mut chars = quoted.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            }));
+        }
+        if !alphabet.is_empty() {
+            for name in name.split(',').map(str::trim) {
+                self.alphabets.insert(strip_indices(name), alphabet.clone());
+            }
+        }
+    }
+}
+
+/// Strips the HTML from a constraint, keeping exponents as `^`
+fn normalize(item: &str) -> String {
+    let item = item.replace("<sup>", "^");
+    let mut text = String::with_capacity(item.len());
+    let mut in_tag = false;
+    for c in item.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&le;", "<=")
+        .replace("&ge;", ">=")
+        .replace('≤', "<=")
+        .replace('≥', ">=")
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `nums[i][j]` becomes `nums[][]`
+fn strip_indices(name: &str) -> String {
+    let mut stripped = String::with_capacity(name.len());
+    let mut in_index = false;
+    for c in name.chars() {
+        match c {
+            '[' => {
+                in_index = true;
+                stripped.push('[');
+            }
+            ']' => {
+                in_index = false;
+                stripped.push(']');
+            }
+            c if !in_index => stripped.push(c),
+            _ => {}
+        }
+    }
+    stripped
+}
+
+/// `The number of nodes in the tree is in the range [0, 10^4].`
+fn parse_range(item: &str) -> Option<Bounds> {
+    let start = item.find('[')?;
+    let end = start + item[start..].find(']')?;
+    let (min, max) = item[start + 1..end].split_once(',')?;
+    let no_parameters = HashMap::new();
+    Some(Bounds {
+        min: eval(min, &no_parameters)?,
+        max: eval(max, &no_parameters)?,
+    })
+}
+
+/// Whether the expression can be evaluated once its parameters are known
+fn is_expr(expr: &str) -> bool {
+    let any_value = identifiers(expr)
+        .into_iter()
+        .map(|name| (name, 1))
+        .collect();
+    eval(expr, &any_value).is_some()
+}
+
+/// Names of the parameters an expression refers to, e.g. `nums.length`
+fn identifiers(expr: &str) -> Vec<String> {
+    let expr: Vec<char> = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut identifiers = vec![];
+    let mut pos = 0;
+    while pos < expr.len() {
+        match eval_identifier(&expr, &mut pos) {
+            Some(identifier) => identifiers.push(identifier),
+            None => pos += 1,
+        }
+    }
+    identifiers
+}
+
+/// Evaluates integer expressions made of `+`, `-`, `*`, `^` and the
+/// values of parameters, e.g. `2 * 10^4` or `nums.length - 1`
+pub fn eval(expr: &str, known: &HashMap<String, i64>) -> Option<i64> {
+    let expr: Vec<char> = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    if expr.is_empty() {
+        return None;
+    }
+    let mut pos = 0;
+    let value = eval_sum(&expr, &mut pos, known)?;
+    if pos != expr.len() {
+        return None;
+    }
+    i64::try_from(value).ok()
+}
+
+fn eval_sum(expr: &[char], pos: &mut usize, known: &HashMap<String, i64>) -> Option<i128> {
+    let mut value = eval_product(expr, pos, known)?;
+    while let Some(&op) = expr.get(*pos) {
+        if op != '+' && op != '-' {
+            break;
+        }
+        *pos += 1;
+        let rhs = eval_product(expr, pos, known)?;
+        value = if op == '+' { value + rhs } else { value - rhs };
+    }
+    Some(value)
+}
+
+fn eval_product(expr: &[char], pos: &mut usize, known: &HashMap<String, i64>) -> Option<i128> {
+    let mut value = eval_power(expr, pos, known)?;
+    while expr.get(*pos) == Some(&'*') {
+        *pos += 1;
+        value = value.checked_mul(eval_power(expr, pos, known)?)?;
+    }
+    Some(value)
+}
+
+fn eval_power(expr: &[char], pos: &mut usize, known: &HashMap<String, i64>) -> Option<i128> {
+    let negative = expr.get(*pos) == Some(&'-');
+    if negative {
+        *pos += 1;
+    }
+    let base = eval_operand(expr, pos, known)?;
+    let value = if expr.get(*pos) == Some(&'^') {
+        *pos += 1;
+        let exponent = u32::try_from(eval_number(expr, pos)?).ok()?;
+        base.checked_pow(exponent)?
+    } else {
+        base
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// A number, or the value of a parameter
+fn eval_operand(expr: &[char], pos: &mut usize, known: &HashMap<String, i64>) -> Option<i128> {
+    if let Some(identifier) = eval_identifier(expr, pos) {
+        return known.get(&identifier).map(|&value| value as i128);
+    }
+    eval_number(expr, pos)
+}
+
+/// `nums[i].length` as `nums[].length`, `.size` as `.length`
+fn eval_identifier(expr: &[char], pos: &mut usize) -> Option<String> {
+    if !expr
+        .get(*pos)
+        .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_')
+    {
+        return None;
+    }
+    let start = *pos;
+    while expr
+        .get(*pos)
+        .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '[' | ']'))
+    {
+        *pos += 1;
+    }
+    let identifier: String = expr[start..*pos].iter().collect();
+    let identifier = strip_indices(&identifier);
+    Some(match identifier.strip_suffix(".size") {
+        Some(name) => format!("{name}.length"),
+        None => identifier,
+    })
+}
+
+fn eval_number(expr: &[char], pos: &mut usize) -> Option<i128> {
+    let start = *pos;
+    while expr.get(*pos).is_some_and(|c| c.is_ascii_digit()) {
+        *pos += 1;
+    }
+    expr[start..*pos].iter().collect::<String>().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(items: &[&str]) -> Constraints {
+        let items: String = items
+            .iter()
+            .map(|item| format!("<li><code>{item}</code></li>"))
+            .collect();
+        Constraints::parse(&format!(
+            "<p><strong>Constraints:</strong></p><ul>{items}</ul>"
+        ))
+    }
+
+    fn range(min: &str, max: &str) -> Range {
+        Range {
+            min: min.to_string(),
+            max: max.to_string(),
+        }
+    }
+
+    fn known(values: &[(&str, i64)]) -> HashMap<String, i64> {
+        values
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value))
+            .collect()
+    }
+
+    #[test]
+    fn evaluates_powers() {
+        let none = HashMap::new();
+        assert_eq!(eval("10^4", &none), Some(10_000));
+        assert_eq!(eval("2 * 10^4", &none), Some(20_000));
+        assert_eq!(eval("-2^31", &none), Some(i32::MIN as i64));
+        assert_eq!(eval("2^31 - 1", &none), Some(i32::MAX as i64));
+        assert_eq!(eval("10^", &none), None);
+        assert_eq!(eval("", &none), None);
+    }
+
+    #[test]
+    fn evaluates_parameters() {
+        let known = known(&[("n", 5), ("nums.length", 3), ("grid[].length", 4)]);
+        assert_eq!(eval("n - 1", &known), Some(4));
+        assert_eq!(eval("nums.length", &known), Some(3));
+        assert_eq!(eval("nums.size", &known), Some(3));
+        assert_eq!(eval("2 * grid[i].length", &known), Some(8));
+        assert_eq!(eval("m", &known), None);
+    }
+
+    #[test]
+    fn parses_html_bounds() {
+        let constraints = parse(&[
+            "-10<sup>9</sup> &lt;= nums[i] &lt;= 10<sup>9</sup>",
+            "1 &lt;= nums.length &lt;= 10<sup>4</sup>",
+        ]);
+        let none = HashMap::new();
+        let element = constraints.elements["nums"].bounds(&none);
+        assert_eq!(
+            element,
+            Some(Bounds {
+                min: -1_000_000_000,
+                max: 1_000_000_000
+            })
+        );
+        let length = constraints.lengths["nums"].bounds(&none);
+        assert_eq!(
+            length,
+            Some(Bounds {
+                min: 1,
+                max: 10_000
+            })
+        );
+    }
+
+    #[test]
+    fn strict_bounds_exclude_their_ends() {
+        let constraints = parse(&["0 &lt; k &lt; n", "-2<sup>31</sup> &lt; x &lt;= 0"]);
+        assert_eq!(constraints.values["k"], range("0 + 1", "n - 1"));
+        let bounds = constraints.values["k"].bounds(&known(&[("n", 5)]));
+        assert_eq!(bounds, Some(Bounds { min: 1, max: 4 }));
+        let bounds = constraints.values["x"].bounds(&HashMap::new());
+        assert_eq!(
+            bounds,
+            Some(Bounds {
+                min: i32::MIN as i64 + 1,
+                max: 0
+            })
+        );
+    }
+
+    #[test]
+    fn bounds_refer_to_lengths() {
+        let constraints = parse(&["1 &lt;= k &lt;= nums.length"]);
+        assert_eq!(constraints.values["k"], range("1", "nums.length"));
+        assert_eq!(constraints.values["k"].references(), ["nums"]);
+        assert_eq!(constraints.values["k"].bounds(&HashMap::new()), None);
+        let bounds = constraints.values["k"].bounds(&known(&[("nums.length", 7)]));
+        assert_eq!(bounds, Some(Bounds { min: 1, max: 7 }));
+    }
+
+    #[test]
+    fn chained_bounds() {
+        let constraints = parse(&[
+            "1 &lt;= k &lt;= n &lt;= 10<sup>5</sup>",
+            "0 &lt;= a, b &lt; n",
+        ]);
+        assert_eq!(constraints.values["k"], range("1", "n"));
+        assert_eq!(constraints.values["n"], range("1", "10^5"));
+        assert_eq!(constraints.values["a"], range("0", "n - 1"));
+        assert_eq!(constraints.values["b"], range("0", "n - 1"));
+    }
+
+    #[test]
+    fn length_aliases() {
+        let constraints = parse(&[
+            "n == nums.length",
+            "1 &lt;= n &lt;= 10<sup>4</sup>",
+            "m == grid.length",
+            "n == grid[i].length",
+        ]);
+        // the last alias of `n` wins
+        assert_eq!(
+            constraints.values["n"],
+            range("grid[].length", "grid[].length")
+        );
+        assert_eq!(constraints.values["m"], range("grid.length", "grid.length"));
+        assert_eq!(constraints.lengths["nums"], range("1", "10^4"));
+    }
+
+    #[test]
+    fn skips_what_it_cant_understand() {
+        let constraints = parse(&[
+            "s consists of lowercase English letters.",
+            "1 &lt;= foo bar",
+        ]);
+        assert!(constraints.values.is_empty());
+        assert_eq!(constraints.alphabets["s"].len(), 26);
+    }
+
+    #[test]
+    fn parses_node_count() {
+        let constraints =
+            parse(&["The number of nodes in the tree is in the range [0, 10<sup>4</sup>]."]);
+        assert_eq!(
+            constraints.nodes,
+            Some(Bounds {
+                min: 0,
+                max: 10_000
+            })
+        );
+    }
+}
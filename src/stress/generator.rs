@@ -0,0 +1,412 @@
+use super::constraints::{eval, Bounds, Constraints, Range};
+use leetcode_runner_cli::file_parser::testcases::Testcases;
+use leetcode_runner_cli::leetcode_api::metadata::{MetaData, ParamType};
+use rand::{rngs::ThreadRng, Rng};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Bounds used for values the question doesn't constrain
+const DEFAULT_VALUES: Bounds = Bounds {
+    min: -100,
+    max: 100,
+};
+/// Longest array / string / tree generated by default.
+/// Small inputs keep the failing cases readable.
+pub const DEFAULT_MAX_LEN: usize = 10;
+
+/// # Random testcase generator
+/// Generates inputs valid as per the parameter types of the
+/// metaData and the bounds of the question constraints.
+///
+/// Parameters are generated in the order their bounds refer to each
+/// other, `k` of `1 <= k <= nums.length` after `nums`, so that bounds
+/// are evaluated with the values of the testcase being generated.
+pub struct Generator<'a> {
+    meta_data: &'a MetaData,
+    constraints: &'a Constraints,
+    max_len: usize,
+    rng: ThreadRng,
+    /// Values and lengths generated so far for the testcase, e.g. `nums.length`
+    known: HashMap<String, i64>,
+}
+
+impl<'a> Generator<'a> {
+    pub fn new(meta_data: &'a MetaData, constraints: &'a Constraints) -> Self {
+        Self {
+            meta_data,
+            constraints,
+            max_len: DEFAULT_MAX_LEN,
+            rng: rand::thread_rng(),
+            known: HashMap::new(),
+        }
+    }
+
+    /// Caps the length of generated arrays, strings, lists and trees
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn generate(&mut self, count: usize) -> Result<Testcases, String> {
+        if self.meta_data.systemdesign {
+            return Err("Generating testcases for design questions is not supported".to_string());
+        }
+        if self.meta_data.params.is_empty() {
+            return Err("Question has no parameters to generate testcases for".to_string());
+        }
+        let order = self.order();
+        let mut testcases = Testcases::default();
+        for _ in 0..count {
+            self.known.clear();
+            let mut case = vec![String::new(); order.len()];
+            for &idx in &order {
+                let param = &self.meta_data.params[idx];
+                let param_type = ParamType::parse(&param.param_type);
+                let value = self.value(&param.name, &param.name, &param_type)?;
+                if let Some(value) = value.as_i64() {
+                    self.known.insert(param.name.clone(), value);
+                }
+                case[idx] = value.to_string();
+            }
+            testcases.cases.push(case.join("\n"));
+        }
+        Ok(testcases)
+    }
+
+    /// Indices of the parameters, those a bound refers to before the bounded
+    /// one. Parameters referring to each other are left in their order.
+    fn order(&self) -> Vec<usize> {
+        let params = &self.meta_data.params;
+        let dependencies: Vec<Vec<usize>> = params
+            .iter()
+            .map(|param| {
+                self.ranges_of(&param.name)
+                    .flat_map(Range::references)
+                    .filter_map(|name| params.iter().position(|other| other.name == name))
+                    .filter(|&other| params[other].name != param.name)
+                    // `grid` isn't generated after `n` of `n == grid[i].length`
+                    // even though its elements refer to `n`
+                    .filter(|&other| !self.aliases(&params[other].name, &param.name))
+                    .collect()
+            })
+            .collect();
+        let mut order: Vec<usize> = vec![];
+        while order.len() < params.len() {
+            let remaining = (0..params.len()).filter(|idx| !order.contains(idx));
+            let ready = remaining.clone().find(|&idx| {
+                dependencies[idx]
+                    .iter()
+                    .all(|dependency| order.contains(dependency))
+            });
+            order.extend(ready.or_else(|| remaining.min()));
+        }
+        order
+    }
+
+    /// Whether the value is one of the lengths of the parameter
+    fn aliases(&self, value: &str, param: &str) -> bool {
+        self.constraints.values.get(value).is_some_and(|range| {
+            range.min == range.max && range.references().iter().any(|name| name == param)
+        })
+    }
+
+    /// Every range constraining the parameter, its elements and lengths included
+    fn ranges_of<'b>(&'b self, name: &'b str) -> impl Iterator<Item = &'b Range> {
+        let of_param = move |key: &&String| {
+            key.strip_prefix(name)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('['))
+        };
+        let constraints = self.constraints;
+        constraints
+            .values
+            .get(name)
+            .into_iter()
+            .chain(constraints.elements.get(name))
+            .chain(
+                constraints
+                    .lengths
+                    .iter()
+                    .filter(move |(key, _)| of_param(key))
+                    .map(|(_, range)| range),
+            )
+    }
+
+    /// Keeps a length as soon as it is decided, along with the values which
+    /// are that length, for the bounds of the elements and of the parameters
+    /// generated after it
+    fn learn_length(&mut self, key: &str, len: usize) {
+        self.known.insert(format!("{key}.length"), len as i64);
+        let constraints = self.constraints;
+        for (name, range) in &constraints.values {
+            if range.min != range.max {
+                continue;
+            }
+            if let Some(value) = eval(&range.min, &self.known) {
+                self.known.insert(name.clone(), value);
+            }
+        }
+    }
+
+    /// Generates a value for `key`, which is either the parameter
+    /// itself or one of its (nested) elements, e.g. `nums[]`
+    fn value(&mut self, name: &str, key: &str, param_type: &ParamType) -> Result<Value, String> {
+        let value = match param_type {
+            ParamType::Integer | ParamType::Long => {
+                let bounds = self.value_bounds(name, key)?;
+                Value::from(self.rng.gen_range(bounds.min..=bounds.max))
+            }
+            ParamType::Double => {
+                let bounds = self.value_bounds(name, key)?;
+                let value = self.rng.gen_range(bounds.min as f64..=bounds.max as f64);
+                Value::from((value * 1e5).round() / 1e5)
+            }
+            ParamType::Boolean => Value::from(self.rng.gen_bool(0.5)),
+            ParamType::Character => Value::from(self.character(name, key).to_string()),
+            ParamType::String => {
+                let len = self.len(key, self.constraints.lengths.get(key))?;
+                self.learn_length(key, len);
+                let string: String = (0..len).map(|_| self.character(name, key)).collect();
+                Value::from(string)
+            }
+            ParamType::List(inner) => {
+                let len = self.len(key, self.constraints.lengths.get(key))?;
+                self.learn_length(key, len);
+                let key = format!("{key}[]");
+                if let ParamType::List(cell) = inner.as_ref() {
+                    // rows of a matrix all have the same length
+                    let row_len = self.len(&key, self.constraints.lengths.get(&key))?;
+                    self.learn_length(&key, row_len);
+                    let cell_key = format!("{key}[]");
+                    let rows = (0..len)
+                        .map(|_| {
+                            (0..row_len)
+                                .map(|_| self.value(name, &cell_key, cell))
+                                .collect::<Result<Vec<_>, _>>()
+                                .map(Value::from)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Value::from(rows)
+                } else {
+                    let items = (0..len)
+                        .map(|_| self.value(name, &key, inner))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Value::from(items)
+                }
+            }
+            ParamType::ListNode => {
+                let len = self.node_count()?;
+                let values = (0..len)
+                    .map(|_| self.node_value())
+                    .collect::<Result<Vec<_>, _>>()?;
+                Value::from(values)
+            }
+            ParamType::TreeNode => self.tree()?,
+            ParamType::Unknown(param_type) => {
+                return Err(format!(
+                    "Generating values of type {} is not supported",
+                    param_type
+                ))
+            }
+        };
+        Ok(value)
+    }
+
+    fn value_bounds(&self, name: &str, key: &str) -> Result<Bounds, String> {
+        let range = if name == key {
+            self.constraints.values.get(name)
+        } else {
+            self.constraints.elements.get(name)
+        };
+        self.resolve(key, range, DEFAULT_VALUES)
+    }
+
+    /// Bounds of the range with the values known so far, the default ones
+    /// when there is no range or it refers to a value which isn't known
+    fn resolve(&self, key: &str, range: Option<&Range>, default: Bounds) -> Result<Bounds, String> {
+        let bounds = range
+            .and_then(|range| range.bounds(&self.known))
+            .unwrap_or(default);
+        if bounds.min > bounds.max {
+            return Err(format!(
+                "Constraints of {} leave no valid value, {} > {}",
+                key, bounds.min, bounds.max
+            ));
+        }
+        Ok(bounds)
+    }
+
+    fn len(&mut self, key: &str, range: Option<&Range>) -> Result<usize, String> {
+        let max_len = self.max_len as i64;
+        let default = Bounds {
+            min: 1,
+            max: max_len,
+        };
+        let bounds = self.resolve(&format!("{key}.length"), range, default)?;
+        Ok(self.capped_len(bounds))
+    }
+
+    /// Random length within the bounds, at most `max_len` unless
+    /// the lower bound of the constraint is above it
+    fn capped_len(&mut self, bounds: Bounds) -> usize {
+        let max_len = self.max_len as i64;
+        let max = bounds.max.min(max_len.max(bounds.min)).max(0);
+        let min = bounds.min.clamp(0, max);
+        self.rng.gen_range(min..=max) as usize
+    }
+
+    fn node_count(&mut self) -> Result<usize, String> {
+        let max_len = self.max_len as i64;
+        let bounds = self.constraints.nodes.unwrap_or(Bounds {
+            min: 1,
+            max: max_len,
+        });
+        if bounds.min > bounds.max {
+            return Err("Constraints of the number of nodes leave no valid value".to_string());
+        }
+        Ok(self.capped_len(bounds))
+    }
+
+    fn character(&mut self, name: &str, key: &str) -> char {
+        let alphabets = &self.constraints.alphabets;
+        match alphabets.get(key).or_else(|| alphabets.get(name)) {
+            Some(alphabet) => alphabet[self.rng.gen_range(0..alphabet.len())],
+            None => self.rng.gen_range('a'..='z'),
+        }
+    }
+
+    fn node_value(&mut self) -> Result<Value, String> {
+        let range = self.constraints.elements.get("Node.val");
+        let bounds = self.resolve("Node.val", range, DEFAULT_VALUES)?;
+        Ok(Value::from(self.rng.gen_range(bounds.min..=bounds.max)))
+    }
+
+    /// Random binary tree in leetcode's level order format
+    fn tree(&mut self) -> Result<Value, String> {
+        let len = self.node_count()?;
+        if len == 0 {
+            return Ok(Value::Array(vec![]));
+        }
+        let mut children: Vec<[Option<usize>; 2]> = vec![[None, None]; len];
+        for node in 1..len {
+            // attach every node to a random free slot of the tree built so far
+            loop {
+                let parent = self.rng.gen_range(0..node);
+                let side = self.rng.gen_range(0..2);
+                if children[parent][side].is_none() {
+                    children[parent][side] = Some(node);
+                    break;
+                }
+            }
+        }
+
+        let mut level_order = vec![self.node_value()?];
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(node) = queue.pop_front() {
+            for child in children[node] {
+                match child {
+                    Some(child) => {
+                        level_order.push(self.node_value()?);
+                        queue.push_back(child);
+                    }
+                    None => level_order.push(Value::Null),
+                }
+            }
+        }
+        while level_order.last() == Some(&Value::Null) {
+            level_order.pop();
+        }
+        Ok(Value::Array(level_order))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(items: &[&str]) -> Constraints {
+        let items: String = items
+            .iter()
+            .map(|item| format!("<li><code>{item}</code></li>"))
+            .collect();
+        Constraints::parse(&format!(
+            "<p><strong>Constraints:</strong></p><ul>{items}</ul>"
+        ))
+    }
+
+    fn meta_data(params: &[(&str, &str)]) -> MetaData {
+        let params: Vec<String> = params
+            .iter()
+            .map(|(name, param_type)| format!(r#"{{"name":"{name}","type":"{param_type}"}}"#))
+            .collect();
+        MetaData::parse(&format!(
+            r#"{{"name":"f","params":[{}],"return":{{"type":"integer"}}}}"#,
+            params.join(",")
+        ))
+        .unwrap()
+    }
+
+    fn cases(meta_data: &MetaData, constraints: &Constraints) -> Vec<Vec<Value>> {
+        Generator::new(meta_data, constraints)
+            .generate(200)
+            .unwrap()
+            .cases
+            .iter()
+            .map(|case| {
+                case.lines()
+                    .map(|line| serde_json::from_str(line).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounds_use_the_generated_length() {
+        // `k` comes first, it is generated after `nums` all the same
+        let meta_data = meta_data(&[("k", "integer"), ("nums", "integer[]")]);
+        let constraints = constraints(&[
+            "1 &lt;= nums.length &lt;= 10<sup>4</sup>",
+            "1 &lt;= k &lt;= nums.length",
+        ]);
+        for case in cases(&meta_data, &constraints) {
+            let k = case[0].as_i64().unwrap();
+            let len = case[1].as_array().unwrap().len() as i64;
+            assert!((1..=len).contains(&k), "k = {k}, nums.length = {len}");
+        }
+    }
+
+    #[test]
+    fn aliased_lengths_match() {
+        let meta_data = meta_data(&[("n", "integer"), ("m", "integer"), ("grid", "integer[][]")]);
+        let constraints = constraints(&[
+            "m == grid.length",
+            "n == grid[i].length",
+            "1 &lt;= m, n &lt;= 200",
+            "0 &lt;= grid[i][j] &lt; n * m",
+        ]);
+        for case in cases(&meta_data, &constraints) {
+            let (n, m) = (case[0].as_i64().unwrap(), case[1].as_i64().unwrap());
+            let grid = case[2].as_array().unwrap();
+            assert_eq!(grid.len() as i64, m);
+            for row in grid {
+                let row = row.as_array().unwrap();
+                assert_eq!(row.len() as i64, n);
+                assert!(row.iter().all(|cell| cell.as_i64().unwrap() < n * m));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_ranges_are_errors() {
+        let meta_data = meta_data(&[("x", "integer")]);
+        let constraints = constraints(&["0 &lt; x &lt; 1"]);
+        let result = Generator::new(&meta_data, &constraints).generate(1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unknown_types_are_errors() {
+        let meta_data = meta_data(&[("x", "Foo")]);
+        let result = Generator::new(&meta_data, &Constraints::default()).generate(1);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,165 @@
+pub mod constraints;
+pub mod generator;
+
+use constraints::Constraints;
+use generator::{Generator, DEFAULT_MAX_LEN};
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::testcases::{Testcases, MAX_CASES_PER_RUN};
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use leetcode_runner_cli::leetcode_api::metadata::MetaData;
+use leetcode_runner_cli::leetcode_api::worker::ExecutionResult;
+use std::time::{Duration, Instant};
+
+/// # Question specification
+/// Everything needed to generate and run inputs for a question,
+/// fetched once and reused for every batch of testcases.
+pub struct QuestionSpec {
+    pub question_id: String,
+    pub meta_data: MetaData,
+    pub constraints: Constraints,
+}
+
+impl QuestionSpec {
+    pub fn fetch(lc: &LeetCode<Authorized>, question_title: &str) -> Result<Self, String> {
+        let question = lc.question_metadata(question_title)?;
+        // inputs are generated from the types of the parameters
+        let meta_data = question.meta_data().map_err(|_| {
+            "Failed to parse question metaData, stress tests need the parameter types to generate inputs"
+                .to_string()
+        })?;
+        let content = lc.question_content(question_title)?.content;
+        Ok(Self {
+            question_id: question.questionId,
+            meta_data,
+            constraints: Constraints::parse(&content),
+        })
+    }
+
+    pub fn generator(&self) -> Generator<'_> {
+        Generator::new(&self.meta_data, &self.constraints)
+    }
+}
+
+/// # Stress test
+/// Runs batches of random testcases against leetcode's reference
+/// solution until an output differs or the budget runs out.
+pub struct StressTest {
+    /// Testcases generated per iteration
+    pub batch_size: usize,
+    pub max_iterations: usize,
+    pub time_budget: Duration,
+    /// Wait between two iterations, to stay within leetcode's rate limits
+    pub delay: Duration,
+}
+
+impl Default for StressTest {
+    fn default() -> Self {
+        Self {
+            batch_size: MAX_CASES_PER_RUN,
+            max_iterations: 20,
+            time_budget: Duration::from_secs(600),
+            delay: Duration::from_secs(3),
+        }
+    }
+}
+
+pub enum StressOutcome {
+    /// No mismatch found within the budget
+    Passed { iterations: usize, testcases: usize },
+    /// Outputs differ from the expected ones
+    Failed {
+        /// Smallest of the failing inputs
+        input: String,
+        output: String,
+        expected: String,
+        /// Every failing input of the batch
+        failed: Testcases,
+    },
+    /// The code failed to compile, crashed or exceeded the limits
+    Aborted {
+        result: Box<ExecutionResult>,
+        /// Inputs of the batch it happened on
+        testcases: Testcases,
+    },
+}
+
+impl StressTest {
+    pub fn run(
+        &self,
+        lc: &LeetCode<Authorized>,
+        codefile: &CodeFile,
+        spec: &QuestionSpec,
+    ) -> Result<StressOutcome, String> {
+        let start = Instant::now();
+        let mut testcases_run = 0;
+        for iteration in 0..self.max_iterations {
+            if iteration > 0 {
+                if start.elapsed() + self.delay >= self.time_budget {
+                    println!("Time budget exhausted");
+                    return Ok(StressOutcome::Passed {
+                        iterations: iteration,
+                        testcases: testcases_run,
+                    });
+                }
+                std::thread::sleep(self.delay);
+            }
+            // inputs grow with every iteration,
+            // so the failures found early are small ones
+            let testcases = spec
+                .generator()
+                .max_len((iteration + 1).min(DEFAULT_MAX_LEN))
+                .generate(self.batch_size)?;
+            println!(
+                "Iteration {}/{} : {} random testcases",
+                iteration + 1,
+                self.max_iterations,
+                testcases.len()
+            );
+            let result =
+                lc.execute_testcases(codefile, &spec.question_id, &spec.meta_data, &testcases)?;
+            let ExecutionResult::Success(result) = result else {
+                return Ok(StressOutcome::Aborted {
+                    result: Box::new(result),
+                    testcases,
+                });
+            };
+            // inputs the reference solution fails on aren't valid ones for
+            // the question, there is no expected output to compare with
+            let rejected = result
+                .cases
+                .iter()
+                .filter(|case| case.expected.is_none())
+                .count();
+            if rejected > 0 {
+                println!(
+                    "Skipped {} testcase(s) the reference solution rejects",
+                    rejected
+                );
+            }
+            testcases_run += testcases.len() - rejected;
+
+            let failed_cases: Vec<usize> = result
+                .failed_cases()
+                .into_iter()
+                .filter(|&idx| result.cases[idx].expected.is_some())
+                .collect();
+            let Some(&smallest) = failed_cases
+                .iter()
+                .min_by_key(|&&idx| testcases.cases[idx].len())
+            else {
+                continue;
+            };
+            let case = &result.cases[smallest];
+            return Ok(StressOutcome::Failed {
+                input: testcases.cases[smallest].clone(),
+                output: case.output.clone().unwrap_or_default(),
+                expected: case.expected.clone().unwrap_or_default(),
+                failed: testcases.select(&failed_cases),
+            });
+        }
+        Ok(StressOutcome::Passed {
+            iterations: self.max_iterations,
+            testcases: testcases_run,
+        })
+    }
+}
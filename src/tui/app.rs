@@ -0,0 +1,259 @@
+use crate::config::Config;
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::language::Language;
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use leetcode_runner_cli::leetcode_api::problems::Problem;
+use leetcode_runner_cli::leetcode_api::worker::{ExecutionResult, SubmissionResult};
+use ratatui::widgets::ListState;
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pane {
+    Problems,
+    Statement,
+    Results,
+}
+
+/// Result shown in the results pane
+pub enum Outcome {
+    Run(Result<ExecutionResult, String>),
+    Submit(Result<SubmissionResult, String>),
+}
+
+/// Question open in the workspace, along with its solution file
+pub struct OpenQuestion {
+    /// Id, title, difficulty and tags of the question
+    pub heading: String,
+    /// Statement as markdown
+    pub statement: String,
+    pub path: PathBuf,
+}
+
+/// # TUI state
+/// Problem list, open question and latest result of the workspace
+pub struct App<'a> {
+    pub lc: &'a LeetCode<Authorized>,
+    /// Progress the client reports while a request is running
+    pub progress: Receiver<String>,
+    pub config: &'a Config,
+    pub language: Language,
+    pub problems: Vec<Problem>,
+    /// Indices of the problems matching the filter
+    pub visible: Vec<usize>,
+    pub list_state: ListState,
+    pub filter: String,
+    pub filtering: bool,
+    pub focus: Pane,
+    pub question: Option<OpenQuestion>,
+    pub statement_scroll: u16,
+    pub outcome: Option<Outcome>,
+    /// Code the outcome is of, to map error lines to the file
+    pub ran_code: Option<CodeFile>,
+    /// Testcases the latest run failed, and the one shown
+    pub failing: Vec<usize>,
+    pub failing_index: usize,
+    pub results_scroll: u16,
+    pub status: String,
+    /// Set by the first press of the submit key, until the second one
+    pub confirming_submit: bool,
+    pub quit: bool,
+}
+
+impl<'a> App<'a> {
+    pub fn new(
+        lc: &'a LeetCode<Authorized>,
+        progress: Receiver<String>,
+        config: &'a Config,
+        problems: Vec<Problem>,
+    ) -> Self {
+        let mut app = Self {
+            lc,
+            progress,
+            config,
+            language: config.language().unwrap_or_default(),
+            visible: (0..problems.len()).collect(),
+            problems,
+            list_state: ListState::default(),
+            filter: String::new(),
+            filtering: false,
+            focus: Pane::Problems,
+            question: None,
+            statement_scroll: 0,
+            outcome: None,
+            ran_code: None,
+            failing: vec![],
+            failing_index: 0,
+            results_scroll: 0,
+            status: "Select a problem and press Enter to open it".to_string(),
+            confirming_submit: false,
+            quit: false,
+        };
+        app.list_state
+            .select((!app.visible.is_empty()).then_some(0));
+        app
+    }
+
+    pub fn selected_problem(&self) -> Option<&Problem> {
+        let selected = self.list_state.selected()?;
+        self.visible.get(selected).map(|&idx| &self.problems[idx])
+    }
+
+    /// Keeps the problems whose id or title contains the filter
+    pub fn apply_filter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = self
+            .problems
+            .iter()
+            .enumerate()
+            .filter(|(_, problem)| {
+                filter.is_empty()
+                    || problem.stat.title.to_lowercase().contains(&filter)
+                    || problem.stat.frontend_question_id.to_string() == filter
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        self.list_state
+            .select((!self.visible.is_empty()).then_some(0));
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let selected = self.list_state.selected().unwrap_or(0) as isize;
+        let last = self.visible.len() as isize - 1;
+        self.list_state
+            .select(Some((selected + delta).clamp(0, last) as usize));
+    }
+
+    pub fn scroll(&mut self, delta: i32) {
+        let scroll = match self.focus {
+            Pane::Problems => return self.move_selection(delta as isize),
+            Pane::Statement => &mut self.statement_scroll,
+            Pane::Results => &mut self.results_scroll,
+        };
+        *scroll = (*scroll as i32 + delta).max(0) as u16;
+    }
+
+    pub fn cycle_focus(&mut self) {
+        self.focus = match self.focus {
+            Pane::Problems => Pane::Statement,
+            Pane::Statement => Pane::Results,
+            Pane::Results => Pane::Problems,
+        };
+    }
+
+    /// Fetches the statement of the selected problem, and creates its
+    /// solution file from the starting code when there is none yet
+    pub fn open_selected(&mut self) {
+        let Some(problem) = self.selected_problem() else {
+            return;
+        };
+        let title_slug = problem.stat.title_slug.clone();
+        let details = match self.lc.question_details(&title_slug) {
+            Ok(details) => details,
+            Err(e) => {
+                self.status = e;
+                return;
+            }
+        };
+        let path = PathBuf::from(self.config.file_name(&title_slug, &self.language));
+        let snippet = details.snippet(&self.language).unwrap_or_default();
+        let region = self.config.region.unwrap_or_default();
+        if let Err(e) = CodeFile::scaffold(&path, &self.language, region, &title_slug, snippet) {
+            self.status = e;
+            return;
+        }
+        let statement = details
+            .content
+            .as_deref()
+            .map(html2md::parse_html)
+            .unwrap_or_else(|| "The statement of paid only questions needs a subscription".into());
+        let tags: Vec<&str> = details
+            .topicTags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect();
+        let heading = format!(
+            "{}. {} ({}) {}",
+            details.questionFrontendId,
+            details.title,
+            details.difficulty,
+            tags.join(", ")
+        );
+        self.status = format!("Opened {}, solution in {}", title_slug, path.display());
+        self.question = Some(OpenQuestion {
+            heading,
+            statement,
+            path,
+        });
+        self.statement_scroll = 0;
+        self.outcome = None;
+        self.ran_code = None;
+        self.failing.clear();
+        self.focus = Pane::Statement;
+    }
+
+    /// Solution file of the open question, read again as it may have been edited
+    pub fn code(&self) -> Result<CodeFile, String> {
+        let Some(question) = &self.question else {
+            return Err("Open a problem first".to_string());
+        };
+        CodeFile::try_from_file(&question.path)
+    }
+
+    /// Shows the outcome of running the default testcases of the code
+    pub fn show_run(
+        &mut self,
+        code: Result<CodeFile, String>,
+        result: Result<ExecutionResult, String>,
+    ) {
+        self.failing = match &result {
+            Ok(ExecutionResult::Success(success)) => success.failed_cases(),
+            _ => vec![],
+        };
+        self.failing_index = 0;
+        self.status = match &result {
+            Ok(ExecutionResult::Success(success)) if success.is_correct() => {
+                "Every testcase passed, press s to submit".to_string()
+            }
+            Ok(ExecutionResult::Success(_)) => {
+                "Press n / N to cycle through the failing testcases".to_string()
+            }
+            _ => "Run finished".to_string(),
+        };
+        self.show(Outcome::Run(result), code.ok());
+    }
+
+    /// Shows the verdict of the submission of the code
+    pub fn show_submission(
+        &mut self,
+        code: Result<CodeFile, String>,
+        result: Result<SubmissionResult, String>,
+    ) {
+        self.failing.clear();
+        self.status = "Submission finished".to_string();
+        self.show(Outcome::Submit(result), code.ok());
+    }
+
+    fn show(&mut self, outcome: Outcome, code: Option<CodeFile>) {
+        self.outcome = Some(outcome);
+        self.ran_code = code;
+        self.results_scroll = 0;
+        self.focus = Pane::Results;
+    }
+
+    pub fn cycle_failing(&mut self, forward: bool) {
+        if self.failing.is_empty() {
+            return;
+        }
+        let len = self.failing.len();
+        self.failing_index = if forward {
+            (self.failing_index + 1) % len
+        } else {
+            (self.failing_index + len - 1) % len
+        };
+        self.results_scroll = 0;
+    }
+}
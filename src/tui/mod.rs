@@ -0,0 +1,195 @@
+mod app;
+mod ui;
+
+use crate::config::Config;
+use app::{App, Pane};
+use leetcode_runner_cli::leetcode_api::leetcode::{Authorized, LeetCode};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::DefaultTerminal;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+/// Lines scrolled by page up / page down
+const PAGE: i32 = 10;
+/// Longest wait for progress before checking whether the request is done
+const PROGRESS_WAIT: Duration = Duration::from_millis(50);
+
+/// # TUI workspace
+/// Problem list, statement and results in one screen, with keys to
+/// run, submit and edit the solution of the open problem.
+/// The progress the client reports on `progress` is shown in the status line.
+pub fn run(
+    lc: &LeetCode<Authorized>,
+    progress: Receiver<String>,
+    config: &Config,
+) -> Result<(), String> {
+    println!("Fetching problems...");
+    let problems = lc.problems()?.sorted();
+    let mut app = App::new(lc, progress, config, problems);
+
+    // results are drawn as plain text, escape codes would show up as is
+    colored::control::set_override(false);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), String> {
+    while !app.quit {
+        draw(terminal, app)?;
+        let event = event::read().map_err(|e| format!("Failed to read input : {e}"))?;
+        let Event::Key(key) = event else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        handle_key(terminal, app, key)?;
+    }
+    Ok(())
+}
+
+fn draw(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), String> {
+    terminal
+        .draw(|frame| ui::draw(frame, app))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to draw : {e}"))
+}
+
+fn handle_key(terminal: &mut DefaultTerminal, app: &mut App, key: KeyEvent) -> Result<(), String> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.quit = true;
+        return Ok(());
+    }
+    if app.filtering {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => app.filtering = false,
+            KeyCode::Backspace => {
+                app.filter.pop();
+                app.apply_filter();
+            }
+            KeyCode::Char(c) => {
+                app.filter.push(c);
+                app.apply_filter();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // any other key cancels a submission waiting for confirmation
+    let confirming_submit = std::mem::take(&mut app.confirming_submit);
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => app.quit = true,
+        KeyCode::Char('/') => {
+            app.filtering = true;
+            app.focus = Pane::Problems;
+        }
+        KeyCode::Tab => app.cycle_focus(),
+        KeyCode::Down | KeyCode::Char('j') => app.scroll(1),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll(-1),
+        KeyCode::PageDown => app.scroll(PAGE),
+        KeyCode::PageUp => app.scroll(-PAGE),
+        KeyCode::Enter => {
+            app.status = "Opening...".to_string();
+            draw(terminal, app)?;
+            app.open_selected();
+        }
+        KeyCode::Char('r') => {
+            app.status = "Running the testcases...".to_string();
+            let code = app.code();
+            let result = blocking(terminal, app, |lc| {
+                lc.execute(code.as_ref().map_err(Clone::clone)?, String::new())
+            })?;
+            app.show_run(code, result);
+        }
+        KeyCode::Char('s') if confirming_submit || !confirm_submit(app) => {
+            app.status = "Submitting...".to_string();
+            let code = app.code();
+            let result = blocking(terminal, app, |lc| {
+                lc.submit(code.as_ref().map_err(Clone::clone)?)
+            })?;
+            app.show_submission(code, result);
+        }
+        KeyCode::Char('s') => {
+            if app.question.is_some() {
+                app.status = "Press s again to submit".to_string();
+                app.confirming_submit = true;
+            } else {
+                app.status = "Open a problem first".to_string();
+            }
+        }
+        KeyCode::Char('e') => edit(terminal, app)?,
+        KeyCode::Char('n') => app.cycle_failing(true),
+        KeyCode::Char('N') => app.cycle_failing(false),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn confirm_submit(app: &App) -> bool {
+    app.config.submit.confirm.unwrap_or(true)
+}
+
+/// Sends a request to leetcode from another thread, the progress
+/// the client reports is shown in the status line meanwhile
+fn blocking<T: Send>(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    request: impl FnOnce(&LeetCode<Authorized>) -> T + Send,
+) -> Result<T, String> {
+    // left over from an earlier request
+    while app.progress.try_recv().is_ok() {}
+    draw(terminal, app)?;
+    let lc = app.lc;
+    std::thread::scope(|scope| {
+        let request = scope.spawn(move || request(lc));
+        while !request.is_finished() {
+            if let Ok(progress) = app.progress.recv_timeout(PROGRESS_WAIT) {
+                app.status = progress.replace('\n', " ");
+                draw(terminal, app)?;
+            }
+        }
+        request
+            .join()
+            .map_err(|_| "The request to leetcode panicked".to_string())
+    })
+}
+
+/// Opens the solution file in `$VISUAL` / `$EDITOR`, `vi` if neither is set
+fn edit(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), String> {
+    let Some(question) = &app.question else {
+        app.status = "Open a problem first".to_string();
+        return Ok(());
+    };
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // the editor may take arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+
+    let _ = disable_raw_mode();
+    let _ = execute!(std::io::stdout(), LeaveAlternateScreen);
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(&question.path)
+        .status();
+    let _ = execute!(std::io::stdout(), EnterAlternateScreen);
+    let _ = enable_raw_mode();
+    terminal
+        .clear()
+        .map_err(|e| format!("Failed to draw : {e}"))?;
+
+    app.status = match status {
+        Ok(status) if status.success() => "Press r to run the testcases".to_string(),
+        Ok(status) => format!("{} exited with {}", program, status),
+        Err(e) => format!("Failed to start {} : {}", program, e),
+    };
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use super::app::{App, Outcome, Pane};
+use leetcode_runner_cli::file_parser::codefile::CodeFile;
+use leetcode_runner_cli::file_parser::line_map::remap_lines;
+use leetcode_runner_cli::leetcode_api::diff;
+use leetcode_runner_cli::leetcode_api::execution::Success;
+use leetcode_runner_cli::leetcode_api::worker::{ExecutionResult, SubmissionResult};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+const KEYS: &str =
+    "Enter open  / filter  Tab pane  j/k scroll  r run  s submit  e edit  n/N failing  q quit";
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(frame.area());
+    let [problems, question] =
+        Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(main);
+    let [statement, results] =
+        Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(question);
+
+    draw_problems(frame, app, problems);
+    draw_statement(frame, app, statement);
+    draw_results(frame, app, results);
+
+    let status_line = if app.filtering {
+        Line::from(vec!["Filter : ".bold(), Span::raw(&app.filter), "▏".into()])
+    } else {
+        Line::from(app.status.as_str())
+    };
+    frame.render_widget(
+        Paragraph::new(vec![status_line, Line::from(KEYS).dim()]),
+        status,
+    );
+}
+
+fn block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused {
+        block.border_style(Style::default().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+fn draw_problems(frame: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .map(|&idx| {
+            let problem = &app.problems[idx];
+            let difficulty = match problem.difficulty.level {
+                1 => problem.difficulty().green(),
+                2 => problem.difficulty().yellow(),
+                _ => problem.difficulty().red(),
+            };
+            let mut spans = vec![
+                Span::raw(format!(
+                    "{} {:>4}. ",
+                    if problem.is_solved() { "✓" } else { " " },
+                    problem.stat.frontend_question_id
+                )),
+                Span::raw(problem.stat.title.as_str()),
+                Span::raw(" "),
+                difficulty,
+            ];
+            if problem.paid_only {
+                spans.push(" (paid)".dim());
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let title = format!("Problems ({}/{})", app.visible.len(), app.problems.len());
+    let list = List::new(items)
+        .block(block(title, app.focus == Pane::Problems))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+fn draw_statement(frame: &mut Frame, app: &App, area: Rect) {
+    let (title, text) = match &app.question {
+        Some(question) => (question.heading.clone(), question.statement.as_str()),
+        None => ("Statement".to_string(), "No problem open"),
+    };
+    let paragraph = Paragraph::new(text)
+        .block(block(title, app.focus == Pane::Statement))
+        .wrap(Wrap { trim: false })
+        .scroll((app.statement_scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_results(frame: &mut Frame, app: &App, area: Rect) {
+    let code = app.ran_code.as_ref();
+    let text = match &app.outcome {
+        None => Text::from("Press r to run the testcases, s to submit"),
+        Some(Outcome::Run(result)) => run_text(result, code, app),
+        Some(Outcome::Submit(result)) => submit_text(result, code),
+    };
+    let paragraph = Paragraph::new(text)
+        .block(block("Results".to_string(), app.focus == Pane::Results))
+        .wrap(Wrap { trim: false })
+        .scroll((app.results_scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+/// Line numbers of errors as lines of the solution file
+fn remap(code: Option<&CodeFile>, message: &str) -> String {
+    match code {
+        Some(code) => remap_lines(code, message),
+        None => message.to_string(),
+    }
+}
+
+fn run_text<'a>(
+    result: &'a Result<ExecutionResult, String>,
+    code: Option<&CodeFile>,
+    app: &App,
+) -> Text<'a> {
+    match result {
+        Ok(ExecutionResult::Success(success)) => success_text(success, app),
+        Ok(ExecutionResult::CompileError(compile_error)) => error_text(
+            "Compile Error",
+            remap(code, &compile_error.full_compile_error),
+        ),
+        Ok(ExecutionResult::RuntimeError(runtime_error)) => error_text(
+            "Runtime Error",
+            remap(code, &runtime_error.full_runtime_error),
+        ),
+        Ok(ExecutionResult::LimitExceeded(limit_exceeded)) => error_text(
+            &limit_exceeded.status_msg,
+            format!(
+                "Time Elapsed : {}\nMemory : {}",
+                limit_exceeded.elapsed_time, limit_exceeded.memory
+            ),
+        ),
+        Ok(ExecutionResult::PendingResult(state)) => {
+            error_text("Pending Result", format!("State : {:?}", state.state()))
+        }
+        Ok(ExecutionResult::Unknown(_)) => error_text("Unknown Error", String::new()),
+        Err(e) => error_text("Some error occured!", e.clone()),
+    }
+}
+
+fn success_text<'a>(success: &'a Success, app: &App) -> Text<'a> {
+    let summary = format!(
+        "Testcases {}/{} passed",
+        success.total_correct, success.total_testcases
+    );
+    let mut lines = vec![
+        if success.is_correct() {
+            Line::from(summary.green().bold())
+        } else {
+            Line::from(summary.red().bold())
+        },
+        Line::from(format!(
+            "Runtime : {}    Memory : {}",
+            success.status_runtime, success.status_memory
+        )),
+        Line::default(),
+    ];
+    let Some(&case_idx) = app.failing.get(app.failing_index) else {
+        return Text::from(lines);
+    };
+    let case = &success.cases[case_idx];
+    lines.push(Line::from(
+        format!(
+            "Testcase {} failed ({} of {} failing)",
+            case_idx + 1,
+            app.failing_index + 1,
+            app.failing.len()
+        )
+        .yellow()
+        .bold(),
+    ));
+    lines.push(Line::from("Input    :"));
+    for (name, value) in &case.input {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::raw(name.as_str()).cyan(),
+            Span::raw(format!(" = {}", value)),
+        ]));
+    }
+    let comparison = match (&case.output, &case.expected) {
+        (Some(output), Some(expected)) => diff::describe(output, expected),
+        (output, expected) => format!(
+            "Output   : {}\nExpected : {}",
+            output.as_deref().unwrap_or("no output"),
+            expected.as_deref().unwrap_or("no expected output")
+        ),
+    };
+    lines.extend(comparison.lines().map(|line| Line::from(line.to_string())));
+    if !case.std_output.is_empty() {
+        lines.push(Line::from("Std Output :"));
+        lines.extend(
+            case.std_output
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+    }
+    Text::from(lines)
+}
+
+fn submit_text(
+    result: &Result<SubmissionResult, String>,
+    code: Option<&CodeFile>,
+) -> Text<'static> {
+    match result {
+        // the status of these is part of their text
+        Ok(SubmissionResult::Success(success)) => Text::from(success.to_string()).green(),
+        Ok(SubmissionResult::Wrong(wrong)) => Text::from(wrong.to_string()).red(),
+        Ok(SubmissionResult::LimitExceeded(limit_exceeded)) => {
+            Text::from(limit_exceeded.to_string()).red()
+        }
+        Ok(SubmissionResult::CompileError(compile_error)) => error_text(
+            "Compile Error",
+            remap(code, &compile_error.full_compile_error),
+        ),
+        Ok(SubmissionResult::RuntimeError(runtime_error)) => error_text(
+            "Runtime Error",
+            remap(code, &runtime_error.full_runtime_error),
+        ),
+        Ok(SubmissionResult::PendingResult(state)) => {
+            error_text("Pending Result", format!("State : {:?}", state.state()))
+        }
+        Ok(SubmissionResult::Unknown(_)) => error_text("Unknown Error", String::new()),
+        Err(e) => error_text("Some error occured!", e.clone()),
+    }
+}
+
+fn error_text(title: &str, message: String) -> Text<'static> {
+    let mut text = Text::from(Line::from(title.to_string().red().bold()));
+    text.extend(Text::from(message));
+    text
+}
@@ -0,0 +1,78 @@
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Quiet time after the last change before the files count as saved
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// # File watcher
+/// Waits for changes of a set of files. The directories of the files
+/// are watched rather than the files themselves, as editors often save
+/// by writing a new file and renaming it over the old one.
+pub struct FileWatcher {
+    // events stop coming in once the watcher is dropped
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    files: Vec<PathBuf>,
+    debounce: Duration,
+}
+
+impl FileWatcher {
+    pub fn new(files: &[&Path], debounce: Duration) -> Result<Self, String> {
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| format!("Failed to start watching : {e}"))?;
+        let mut watched = vec![];
+        for file in files {
+            let file = file
+                .canonicalize()
+                .map_err(|_| format!("Failed to watch {}", file.display()))?;
+            let Some(dir) = file.parent() else {
+                return Err(format!("Failed to watch {}", file.display()));
+            };
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(|e| format!("Failed to watch {} : {e}", dir.display()))?;
+            watched.push(file);
+        }
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            files: watched,
+            debounce,
+        })
+    }
+
+    /// Blocks until one of the files changed, and then until
+    /// no further change came in for the debounce duration
+    pub fn wait(&self) -> Result<(), String> {
+        loop {
+            let event = self
+                .events
+                .recv()
+                .map_err(|_| "Stopped watching the files".to_string())?;
+            if self.is_relevant(event) {
+                break;
+            }
+        }
+        loop {
+            match self.events.recv_timeout(self.debounce) {
+                // every change restarts the quiet time, changes to
+                // other files of the directories included
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => return Ok(()),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("Stopped watching the files".to_string())
+                }
+            }
+        }
+    }
+
+    fn is_relevant(&self, event: notify::Result<Event>) -> bool {
+        let Ok(event) = event else {
+            return false;
+        };
+        !event.kind.is_access() && event.paths.iter().any(|path| self.files.contains(path))
+    }
+}